@@ -0,0 +1,340 @@
+pub mod render;
+
+use crate::{
+    parse_tree::parse::error::{describe_expected, ParserError, UnexpectedToken},
+    semantic_model::{error::SemanticError, format::FormatSpec},
+    string::StringSlice,
+    tokenizer::{token::TokenKind, TokenizeError},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Level {
+    Error,
+    Warning,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Label {
+    pub slice: StringSlice,
+    pub message: String,
+}
+
+/// An error or warning ready to be rendered, the primary label is what the message is about,
+/// secondary labels point at related code
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    pub level: Level,
+    pub message: String,
+    pub primary: Option<Label>,
+    pub secondary: Vec<Label>,
+    pub notes: Vec<String>,
+    pub help: Vec<String>,
+}
+
+impl Diagnostic {
+    pub fn error(message: impl Into<String>) -> Self {
+        return Self {
+            level: Level::Error,
+            message: message.into(),
+            primary: None,
+            secondary: vec![],
+            notes: vec![],
+            help: vec![],
+        };
+    }
+
+    pub fn with_label(mut self, slice: &StringSlice, message: impl Into<String>) -> Self {
+        self.primary = Some(Label {
+            slice: slice.clone(),
+            message: message.into(),
+        });
+        return self;
+    }
+
+    pub fn with_secondary(mut self, slice: &StringSlice, message: impl Into<String>) -> Self {
+        self.secondary.push(Label {
+            slice: slice.clone(),
+            message: message.into(),
+        });
+        return self;
+    }
+
+    pub fn with_note(mut self, note: impl Into<String>) -> Self {
+        self.notes.push(note.into());
+        return self;
+    }
+
+    pub fn with_help(mut self, help: impl Into<String>) -> Self {
+        self.help.push(help.into());
+        return self;
+    }
+}
+
+impl From<&TokenizeError> for Diagnostic {
+    fn from(error: &TokenizeError) -> Self {
+        return match error {
+            TokenizeError::InvalidString(slice) => Diagnostic::error("invalid string literal")
+                .with_label(slice, "")
+                .with_help("multi-line strings start with `\"\"\"` followed by a new line"),
+            TokenizeError::InvalidChar(slice) => {
+                Diagnostic::error(format!("unexpected character `{}`", slice.value()))
+                    .with_label(slice, "not valid here")
+            }
+            TokenizeError::UnclosedChar(slice) => Diagnostic::error("unclosed char literal")
+                .with_label(slice, "expected a `'` after a single character"),
+            TokenizeError::InvalidEscape(slice) => {
+                Diagnostic::error(format!("invalid escape `{}`", slice.value()))
+                    .with_label(slice, "")
+                    .with_help("valid escapes are `\\n`, `\\r`, `\\t`, `\\0`, `\\\\`, `\\\"`, `\\'`, `\\x00` to `\\x7F` and `\\u{...}`")
+            }
+            TokenizeError::UnclosedStr(slice) => Diagnostic::error("unclosed string literal")
+                .with_label(slice, "the string starts here")
+                .with_help("use a raw `r\"...\"` or a `\"\"\"` string to span multiple lines"),
+            TokenizeError::InvalidNumber(slice) => {
+                Diagnostic::error(format!("invalid number `{}`", slice.value()))
+                    .with_label(slice, "")
+            }
+            TokenizeError::InvalidSuffix(slice) => {
+                Diagnostic::error(format!("invalid suffix `{}` for a number", slice.value()))
+                    .with_label(slice, "")
+                    .with_help("the suffix has to be a numeric type like `u8` or `f32`")
+            }
+            TokenizeError::UnexpectedEof(slice) => Diagnostic::error("unexpected end of file")
+                .with_label(slice, "a literal or comment isn't closed before here"),
+        };
+    }
+}
+
+impl From<&ParserError> for Diagnostic {
+    fn from(error: &ParserError) -> Self {
+        return match error {
+            ParserError::TokenizeError(error) => error.into(),
+            ParserError::UnexpectedToken(box UnexpectedToken {
+                token,
+                expected,
+                context,
+                throwing_location,
+            }) => {
+                let found = match token.kind {
+                    TokenKind::Eof => "end of file".to_string(),
+                    _ => format!("`{}`", token.slice.value()),
+                };
+
+                let message = match context.is_empty() {
+                    true => format!("expected {}", describe_expected(expected)),
+                    false => format!("expected {} {context}", describe_expected(expected)),
+                };
+                let diagnostic =
+                    Diagnostic::error(message).with_label(&token.slice, format!("found {found}"));
+
+                // Only interesting when working on the parser itself
+                match throwing_location {
+                    Some(location) => diagnostic.with_note(format!("raised at {location}")),
+                    None => diagnostic,
+                }
+            }
+        };
+    }
+}
+
+impl From<&SemanticError> for Diagnostic {
+    fn from(error: &SemanticError) -> Self {
+        return match error {
+            SemanticError::DuplicateSymbol {
+                name,
+                first,
+                second,
+            } => Diagnostic::error(format!("`{name}` is declared more than once"))
+                .with_label(second, "redeclared here")
+                .with_secondary(first, "first declared here"),
+            SemanticError::MissingImport { slice, path } => {
+                Diagnostic::error(format!("can't find `{}`", path.display()))
+                    .with_label(slice, "imported here")
+            }
+            SemanticError::UnsupportedImport { slice, path } => {
+                Diagnostic::error(format!("can't import `{}`", path.display()))
+                    .with_label(slice, "")
+                    .with_note("only `.gek` files can be imported")
+            }
+            SemanticError::ImportCycle { slice, cycle } => {
+                let cycle: Vec<_> = cycle.iter().map(|it| it.display().to_string()).collect();
+                Diagnostic::error("import cycle")
+                    .with_label(slice, "this import closes the cycle")
+                    .with_note(cycle.join(" -> "))
+            }
+            SemanticError::ParseError { path, error } => {
+                Diagnostic::from(error).with_note(format!("while parsing `{}`", path.display()))
+            }
+            SemanticError::UnknownPath { path } => {
+                Diagnostic::error(format!("can't find `{}`", path.path.join("::")))
+                    .with_label(&path.slice, "not found in this scope")
+            }
+            SemanticError::AmbiguousPath { path, candidates } => {
+                let mut diagnostic =
+                    Diagnostic::error(format!("`{}` is ambiguous", path.path.join("::")))
+                        .with_label(&path.slice, "");
+                for candidate in candidates {
+                    diagnostic =
+                        diagnostic.with_note(format!("could be `{}`", candidate.join("::")));
+                }
+                diagnostic
+            }
+            SemanticError::NotAType { path } => {
+                Diagnostic::error(format!("`{}` isn't a type", path.path.join("::")))
+                    .with_label(&path.slice, "expected a type")
+            }
+            SemanticError::GenericArity {
+                slice,
+                expected,
+                found,
+            } => Diagnostic::error(format!(
+                "expected {expected} generic arguments, found {found}"
+            ))
+            .with_label(slice, ""),
+            SemanticError::InfiniteSize { slice, cycle } => {
+                let cycle: Vec<_> = cycle.iter().map(|it| it.join("::")).collect();
+                Diagnostic::error("struct has an infinite size")
+                    .with_label(slice, "contains itself")
+                    .with_note(cycle.join(" -> "))
+                    .with_help("put the recursive field behind a reference")
+            }
+            SemanticError::TypeMismatch {
+                slice,
+                expected,
+                found,
+            } => Diagnostic::error("mismatched types")
+                .with_label(slice, format!("expected `{expected}`, found `{found}`")),
+            SemanticError::InvalidOperands {
+                slice,
+                op,
+                lhs,
+                rhs,
+            } => Diagnostic::error(format!("can't apply `{op}` to `{lhs}` and `{rhs}`"))
+                .with_label(slice, ""),
+            SemanticError::InvalidOperand { slice, op, ty } => {
+                Diagnostic::error(format!("can't apply `{op}` to `{ty}`")).with_label(slice, "")
+            }
+            SemanticError::ArgumentCount {
+                slice,
+                expected,
+                found,
+            } => Diagnostic::error(format!("expected {expected} arguments, found {found}"))
+                .with_label(slice, ""),
+            SemanticError::ArrayLength {
+                slice,
+                expected,
+                found,
+            } => Diagnostic::error(format!(
+                "expected an array of {expected} elements, found {found}"
+            ))
+            .with_label(slice, ""),
+            SemanticError::NotCallable { slice, ty } => {
+                Diagnostic::error(format!("`{ty}` can't be called")).with_label(slice, "")
+            }
+            SemanticError::NotIndexable { slice, ty } => {
+                Diagnostic::error(format!("`{ty}` can't be indexed")).with_label(slice, "")
+            }
+            SemanticError::InvalidAssignment { slice } => {
+                Diagnostic::error("can't assign to this expression")
+                    .with_label(slice, "")
+                    .with_note(
+                        "only variables, fields, indexes and dereferences can be assigned to",
+                    )
+            }
+            SemanticError::ImmutableAssignment { slice } => {
+                Diagnostic::error(format!("can't assign to `{}`", slice.value()))
+                    .with_label(slice, "not mutable")
+                    .with_help("declare it with `mut` or take a `ref mut` to it")
+            }
+            SemanticError::NotIterable { slice, ty } => {
+                Diagnostic::error(format!("`{ty}` can't be iterated over"))
+                    .with_label(slice, "")
+                    .with_note("`for in` loops iterate over ranges")
+            }
+            SemanticError::UnknownField { slice, ty, field } => {
+                Diagnostic::error(format!("`{ty}` has no field `{field}`"))
+                    .with_label(slice, "unknown field")
+            }
+            SemanticError::InvalidAccess { slice, access, ty } => {
+                Diagnostic::error(format!("can't use `{access}` on `{ty}`")).with_label(slice, "")
+            }
+            SemanticError::InvalidCast { slice, from, to } => {
+                Diagnostic::error(format!("can't cast `{from}` to `{to}`")).with_label(slice, "")
+            }
+            SemanticError::LiteralOutOfRange { slice, ty } => {
+                Diagnostic::error(format!("literal out of range for `{ty}`")).with_label(slice, "")
+            }
+            SemanticError::InvalidFormat { slice } => Diagnostic::error("invalid format string")
+                .with_label(slice, "")
+                .with_help(
+                    "placeholders look like `{}`, `{name}` or `{:x}`, use `{{` and `}}` for braces",
+                ),
+            SemanticError::FormatNotLiteral { slice } => {
+                Diagnostic::error("format strings have to be string literals").with_label(slice, "")
+            }
+            SemanticError::FormatArgCount {
+                slice,
+                expected,
+                found,
+            } => Diagnostic::error(format!(
+                "expected {expected} format arguments, found {found}"
+            ))
+            .with_label(slice, "one argument per `{}`"),
+            SemanticError::NotFormattable { slice, spec, ty } => {
+                let diagnostic =
+                    Diagnostic::error(format!("`{ty}` can't be formatted")).with_label(slice, "");
+                match spec {
+                    FormatSpec::Display => {
+                        diagnostic.with_help(format!("implement `Display` for `{ty}`"))
+                    }
+                    _ => diagnostic.with_note("number bases only work on integers"),
+                }
+            }
+            SemanticError::UnknownAttribute { path } => Diagnostic::error(format!(
+                "unknown attribute `{}`",
+                path.path.join("::")
+            ))
+            .with_label(&path.slice, "")
+            .with_note(
+                "the built-in attributes are `Derive`, `Flags`, `OkWhen`, `NoneWhen` and `Extern`",
+            ),
+            SemanticError::MisplacedAttribute {
+                slice,
+                name,
+                allowed,
+            } => Diagnostic::error(format!("`{name}` can only be used on {allowed}"))
+                .with_label(slice, ""),
+            SemanticError::InvalidAttributeParams {
+                slice,
+                name,
+                expected,
+            } => Diagnostic::error(format!("invalid params for `{name}`"))
+                .with_label(slice, "")
+                .with_help(format!("`{name}` takes {expected}")),
+            SemanticError::DuplicateAttribute {
+                name,
+                first,
+                second,
+            } => Diagnostic::error(format!("`{name}` is used more than once"))
+                .with_label(second, "repeated here")
+                .with_secondary(first, "first used here"),
+            SemanticError::DuplicateDerive {
+                name,
+                first,
+                second,
+            } => Diagnostic::error(format!("`{name}` is derived more than once"))
+                .with_label(second, "derived again here")
+                .with_secondary(first, "first derived here"),
+            SemanticError::InvalidFlag { slice } => {
+                Diagnostic::error("flags have to be a single bit")
+                    .with_label(slice, "")
+                    .with_help("give the variant a power of two, like `0x4`")
+            }
+            SemanticError::UncheckedFlag { slice } => {
+                Diagnostic::error("can't check that this flag is a single bit")
+                    .with_label(slice, "")
+                    .with_note("only int literals and the operators on them can be checked")
+            }
+        };
+    }
+}
@@ -0,0 +1,248 @@
+use std::fmt::Write;
+
+use crate::string::source::{FileId, SourceMap};
+
+use super::{Diagnostic, Level};
+
+const RESET: &str = "\x1b[0m";
+const BOLD: &str = "\x1b[1m";
+const RED: &str = "\x1b[1;31m";
+const YELLOW: &str = "\x1b[1;33m";
+const BLUE: &str = "\x1b[1;34m";
+
+/// Renders diagnostics like rustc does, with the source lines the labels point at
+pub struct Renderer<'a> {
+    sources: &'a SourceMap,
+    color: bool,
+}
+
+impl<'a> Renderer<'a> {
+    pub fn new(sources: &'a SourceMap, color: bool) -> Self {
+        return Self { sources, color };
+    }
+
+    fn paint(&self, text: &str, style: &str) -> String {
+        return match self.color {
+            true => format!("{style}{text}{RESET}"),
+            false => text.to_string(),
+        };
+    }
+
+    pub fn render(&self, diagnostic: &Diagnostic) -> String {
+        let mut out = String::new();
+
+        let (level, color) = match diagnostic.level {
+            Level::Error => ("error", RED),
+            Level::Warning => ("warning", YELLOW),
+        };
+        writeln!(
+            out,
+            "{}{}",
+            self.paint(level, color),
+            self.paint(&format!(": {}", diagnostic.message), BOLD)
+        )
+        .unwrap();
+
+        // Labels of sources that aren't in the map can't be shown
+        let labels: Vec<_> = diagnostic
+            .primary
+            .iter()
+            .map(|it| (it, true))
+            .chain(diagnostic.secondary.iter().map(|it| (it, false)))
+            .filter_map(|(label, primary)| {
+                let location = label.slice.location(self.sources)?;
+                return Some((label, primary, location));
+            })
+            .collect();
+
+        let width = labels
+            .iter()
+            .map(|(_, _, location)| location.start.line.to_string().len())
+            .max()
+            .unwrap_or(0);
+        let pad = " ".repeat(width);
+        let gutter = self.paint("|", BLUE);
+
+        let mut file: Option<FileId> = None;
+
+        for (label, primary, location) in &labels {
+            if file != Some(location.file) {
+                let arrow = match file {
+                    None => "-->",
+                    Some(_) => ":::",
+                };
+                writeln!(out, "{pad}{} {location}", self.paint(arrow, BLUE)).unwrap();
+                writeln!(out, "{pad} {gutter}").unwrap();
+                file = Some(location.file);
+            }
+
+            let source = self.sources.get(location.file).unwrap();
+            let line = source.line(location.start.line).unwrap_or("");
+
+            let number = format!("{:>width$}", location.start.line);
+            writeln!(out, "{} {gutter} {line}", self.paint(&number, BLUE)).unwrap();
+
+            // Tabs are kept so the carets line up however wide they're shown
+            let indent: String = line
+                .chars()
+                .take(location.start.col - 1)
+                .map(|it| if it == '\t' { '\t' } else { ' ' })
+                .collect();
+
+            let len = match location.end.line == location.start.line {
+                true => location.end.col - location.start.col,
+                false => line.chars().count() + 1 - location.start.col,
+            };
+
+            let (marker, color) = match primary {
+                true => ("^", RED),
+                false => ("-", BLUE),
+            };
+            let mut underline = marker.repeat(len.max(1));
+            if !label.message.is_empty() {
+                write!(underline, " {}", label.message).unwrap();
+            }
+
+            writeln!(
+                out,
+                "{pad} {gutter} {indent}{}",
+                self.paint(&underline, color)
+            )
+            .unwrap();
+        }
+
+        if !diagnostic.notes.is_empty() || !diagnostic.help.is_empty() {
+            if !labels.is_empty() {
+                writeln!(out, "{pad} {gutter}").unwrap();
+            }
+
+            let notes = diagnostic.notes.iter().map(|it| ("note", it));
+            let help = diagnostic.help.iter().map(|it| ("help", it));
+
+            for (kind, text) in notes.chain(help) {
+                writeln!(
+                    out,
+                    "{pad} {} {}: {text}",
+                    self.paint("=", BLUE),
+                    self.paint(kind, BOLD)
+                )
+                .unwrap();
+            }
+        }
+
+        return out;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::PathBuf;
+
+    use crate::{
+        diagnostic::Diagnostic,
+        parse_tree::parse::parse_root,
+        semantic_model::error::SemanticError,
+        string::source::SourceMap,
+        tokenizer::{token::TokenKind, Tokenizer},
+    };
+
+    use super::Renderer;
+
+    #[test]
+    fn snippets() {
+        let mut sources = SourceMap::new();
+        let file = sources.add(
+            PathBuf::from("Main.gek"),
+            "func Main() {\n\tlet x: i32 = \"hé\";\n}\n".into(),
+        );
+        let src = sources.get(file).unwrap().src.clone();
+
+        let mut tokenizer = Tokenizer::for_file(file, src.clone());
+        let mut tokens = vec![];
+        loop {
+            let token = tokenizer.next().unwrap();
+            if token.kind == TokenKind::Eof {
+                break;
+            }
+            tokens.push(token);
+        }
+
+        let name = &tokens[1].slice;
+        let string = &tokens[10].slice;
+        assert_eq!(&*string.value(), "\"hé\"");
+
+        let diagnostic = Diagnostic::error("mismatched types")
+            .with_label(string, "expected `i32`, found `ref str`")
+            .with_secondary(name, "in here")
+            .with_help("use a number");
+
+        assert_eq!(
+            Renderer::new(&sources, false).render(&diagnostic),
+            concat!(
+                "error: mismatched types\n",
+                " --> Main.gek:2:15\n",
+                "  |\n",
+                "2 | \tlet x: i32 = \"hé\";\n",
+                "  | \t             ^^^^ expected `i32`, found `ref str`\n",
+                "1 | func Main() {\n",
+                "  |      ---- in here\n",
+                "  |\n",
+                "  = help: use a number\n",
+            )
+        );
+
+        let colored = Renderer::new(&sources, true).render(&diagnostic);
+        assert!(colored.starts_with("\x1b[1;31merror\x1b[0m"));
+    }
+
+    #[test]
+    fn parse_errors() {
+        let mut sources = SourceMap::new();
+        let file = sources.add(
+            PathBuf::from("Main.gek"),
+            "func Main() {\n  let = 1;\n}".into(),
+        );
+        let src = sources.get(file).unwrap().src.clone();
+
+        let error = parse_root(&mut Tokenizer::for_file(file, src)).unwrap_err();
+        let error = SemanticError::ParseError {
+            path: PathBuf::from("Main.gek"),
+            error,
+        };
+
+        let rendered = Renderer::new(&sources, false).render(&(&error).into());
+        let lines: Vec<_> = rendered.lines().collect();
+
+        assert_eq!(
+            lines[0],
+            "error: expected an identifier or `_` in a variable declaration"
+        );
+        assert_eq!(lines[1], " --> Main.gek:2:7");
+        assert_eq!(lines[3], "2 |   let = 1;");
+        assert_eq!(lines[4], "  |       ^ found `=`");
+    }
+
+    #[test]
+    fn unexpected_eof() {
+        let mut sources = SourceMap::new();
+        let file = sources.add(PathBuf::from("Main.gek"), "func Main() {}\n/* x".into());
+        let src = sources.get(file).unwrap().src.clone();
+
+        let error = parse_root(&mut Tokenizer::for_file(file, src)).unwrap_err();
+        let error = SemanticError::ParseError {
+            path: PathBuf::from("Main.gek"),
+            error,
+        };
+
+        let rendered = Renderer::new(&sources, false).render(&(&error).into());
+        let lines: Vec<_> = rendered.lines().collect();
+
+        assert_eq!(lines[0], "error: unexpected end of file");
+        assert_eq!(lines[1], " --> Main.gek:2:5");
+        assert_eq!(
+            lines[4],
+            "  |     ^ a literal or comment isn't closed before here"
+        );
+        assert_eq!(lines[6], "  = note: while parsing `Main.gek`");
+    }
+}
@@ -1,21 +1,77 @@
 #![feature(decl_macro, let_chains, assert_matches, box_patterns)]
 
-use std::{fs::File, io::Read};
+use std::{
+    env,
+    io::{self, IsTerminal},
+    path::Path,
+    process::ExitCode,
+};
 
-use parse_tree::parse::parse_root;
-use tokenizer::Tokenizer;
+use diagnostic::{render::Renderer, Diagnostic};
+use semantic_model::{
+    attrs::expand_attrs,
+    import::{ImportGraph, ImportResolver},
+    namespace::NamespaceTree,
+    resolve::resolve_paths,
+    structs::check_struct_sizes,
+    symbol::SymbolTable,
+    typecheck::check_types,
+};
+use string::source::SourceMap;
 
+pub mod diagnostic;
 pub mod parse_tree;
 pub mod semantic_model;
 pub mod string;
 pub mod tokenizer;
 
-fn main() {
-    let mut file = File::open("test/Main.gek").unwrap();
-    let mut src = String::new();
-    file.read_to_string(&mut src).unwrap();
+fn main() -> ExitCode {
+    let resolver = ImportResolver::new(env::current_dir().unwrap());
+    let color = io::stderr().is_terminal() && env::var_os("NO_COLOR").is_none();
 
-    let mut tokenizer = Tokenizer::new(src.into());
+    let entry = Path::new("test/Main.gek");
+    let mut errors = vec![];
+    let mut graph = match ImportGraph::load(&resolver, entry, &mut errors) {
+        Ok(graph) => graph,
+        Err(error) => {
+            let diagnostic = Diagnostic::error(format!("can't read `{}`", entry.display()))
+                .with_note(error.to_string());
+            eprintln!(
+                "{}",
+                Renderer::new(&SourceMap::new(), color).render(&diagnostic)
+            );
+            return ExitCode::FAILURE;
+        }
+    };
 
-    println!("{:#?}", parse_root(&mut tokenizer));
+    for file in &mut graph.files {
+        expand_attrs(&mut file.tree, &mut errors);
+    }
+
+    let mut tables = vec![];
+    for file in &graph.files {
+        tables.push(SymbolTable::build(&file.tree, &mut errors));
+    }
+
+    let tree = NamespaceTree::build(&tables, &mut errors);
+
+    let mut scopes = vec![];
+    for (idx, file) in graph.files.iter().enumerate() {
+        scopes.push(tree.scope(idx, &file.tree, &mut errors));
+    }
+
+    resolve_paths(&scopes, &graph.files, &mut errors);
+    check_struct_sizes(&scopes, &mut errors);
+    check_types(&scopes, &tables, &graph.files, &mut errors);
+
+    let renderer = Renderer::new(&graph.sources, color);
+
+    for error in &errors {
+        eprintln!("{}", renderer.render(&Diagnostic::from(error)));
+    }
+
+    return match errors.is_empty() {
+        true => ExitCode::SUCCESS,
+        false => ExitCode::FAILURE,
+    };
 }
@@ -2,11 +2,11 @@ use std::{fmt::Debug, sync::Arc};
 
 use crate::{
     string::StringSlice,
-    tokenizer::token::{Keyword, TokenKind},
+    tokenizer::token::{Keyword, OperatorTrait, TokenKind},
 };
 
 use super::{
-    expr::Expr,
+    expr::{Expr, PrimitiveExpr},
     statement::{Block, FunctionModifier, VariableModifier, VariableName},
     types::{RefKind, Type},
     IdentPath,
@@ -18,6 +18,7 @@ where
     T: Debug + Clone + PartialEq,
 {
     pub slice: StringSlice,
+    pub attrs: Option<Attrs>,
     pub generics: Option<GenericsDecl>,
     pub is_pub: bool,
     pub value: T,
@@ -38,6 +39,8 @@ pub enum DeclLvl1Kind {
     Trait(TraitDecl),
     Impl(ImplDecl),
     Lvl2(DeclLvl2),
+    /// A declaration that failed to parse
+    Error,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -52,6 +55,13 @@ pub struct NamespaceDecl {
     pub path: IdentPath,
 }
 
+#[derive(Debug, Clone, PartialEq)]
+pub struct UsingDecl {
+    pub slice: StringSlice,
+    pub path: IdentPath,
+    pub alias: Option<Arc<str>>,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct ImplDecl {
     pub slice: StringSlice,
@@ -63,7 +73,9 @@ pub struct ImplDecl {
 #[derive(Debug, Clone, PartialEq)]
 pub struct TraitDecl {
     pub slice: StringSlice,
+    /// Operator traits are named like they're written, like `_!`
     pub name: Arc<str>,
+    pub operator: Option<OperatorTrait>,
     pub body: TraitBody,
 }
 
@@ -128,6 +140,32 @@ pub struct FunctionDecl {
     pub params: Vec<FuncParam>,
     pub ret: Option<Type>,
     pub body: Option<FuncBody>,
+    /// Set by `#[Extern]` when attributes are expanded
+    pub linkage: Option<Linkage>,
+    /// Set by `#[OkWhen]` and `#[NoneWhen]` when attributes are expanded
+    pub sentinel: Option<Sentinel>,
+}
+
+/// How a function is exposed to, or found in, code written in another language
+#[derive(Debug, Clone, PartialEq)]
+pub struct Linkage {
+    pub abi: Abi,
+    /// The name of the symbol, the function's own name unless `symbol = "..."` is given
+    pub symbol: Arc<str>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Abi {
+    C,
+}
+
+/// A return value with a special meaning, so `_!` works on functions that don't return an option
+#[derive(Debug, Clone, PartialEq)]
+pub enum Sentinel {
+    /// Returning anything else is an error
+    OkWhen(PrimitiveExpr),
+    /// Returning it means there's no value
+    NoneWhen(PrimitiveExpr),
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -165,31 +203,36 @@ pub enum ClauseKind {
     Default,
 }
 
-// #[derive(Debug, Clone, PartialEq)]
-// pub struct Attrs {
-//     pub slice: StringSlice,
-//     pub attrs: Vec<Attr>,
-// }
+/// Every `#[...]` in front of a declaration, field or variant
+#[derive(Debug, Clone, PartialEq)]
+pub struct Attrs {
+    pub slice: StringSlice,
+    pub attrs: Vec<Attr>,
+}
 
-// #[derive(Debug, Clone, PartialEq)]
-// pub struct Attr {
-//     pub slice: StringSlice,
-//     pub name: Arc<str>,
-//     pub params: Vec<AttrParam>,
-// }
+/// `Name` or `Name(params...)`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Attr {
+    pub slice: StringSlice,
+    pub name: IdentPath,
+    pub params: Vec<AttrParam>,
+}
 
-// #[derive(Debug, Clone, PartialEq)]
-// pub struct AttrParam {
-//     pub slice: StringSlice,
-//     pub name: Option<Arc<str>>,
-//     pub value: AttrParamKind,
-// }
+/// A positional param like `C`, or a named one like `symbol = "glClearColor"`
+#[derive(Debug, Clone, PartialEq)]
+pub struct AttrParam {
+    pub slice: StringSlice,
+    pub name: Option<Arc<str>>,
+    pub value: AttrParamKind,
+}
 
-// #[derive(Debug, Clone, PartialEq)]
-// pub enum AttrParamKind {
-//     Value(PrimitiveExpr),
-//     Ident(Arc<str>),
-// }
+#[derive(Debug, Clone, PartialEq)]
+pub enum AttrParamKind {
+    Value(PrimitiveExpr),
+    Path(IdentPath),
+    /// A param with params of its own, like `Any(A, B)`
+    Attr(Attr),
+}
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct FuncBody {
@@ -227,6 +270,7 @@ pub struct StructBody {
 #[derive(Debug, Clone, PartialEq)]
 pub struct StructParam {
     pub slice: StringSlice,
+    pub attrs: Option<Attrs>,
     pub is_pub: bool,
     pub name: Arc<str>,
     pub ty: Type,
@@ -253,6 +297,7 @@ pub struct IntEnumBody {
 #[derive(Debug, Clone, PartialEq)]
 pub struct IntEnumParam {
     pub slice: StringSlice,
+    pub attrs: Option<Attrs>,
     pub name: Arc<str>,
     pub value: Option<Expr>,
 }
@@ -1,4 +1,4 @@
-use std::sync::Arc;
+use std::{fmt::Display, sync::Arc};
 
 use crate::{
     string::StringSlice,
@@ -42,6 +42,12 @@ pub enum ExprKind {
         op: UnaryOp,
         value: Box<Expr>,
     },
+    /// `target = value` or a compound assignment like `target += value`
+    Assign {
+        target: Box<Expr>,
+        op: AssignOp,
+        value: Box<Expr>,
+    },
     Variable {
         path: IdentPath,
         generics: Option<GenericsInstance>,
@@ -54,6 +60,7 @@ pub enum ExprKind {
     AnonStructInitializer {
         list: InitializerList,
     },
+    Array(ArrayInitializer),
     Lambda {
         params: Option<LambdaParams>,
         captures: Option<LambdaCaptures>,
@@ -77,6 +84,8 @@ pub struct PrimitiveExpr {
 pub enum PrimitiveExprKind {
     Number(Number),
     String(Arc<str>),
+    ByteString(Arc<[u8]>),
+    CString(Arc<[u8]>),
     Char(char),
     Bool(bool),
     Default,
@@ -125,6 +134,17 @@ pub enum InitializerKind {
     Empty,
 }
 
+/// `[1, 2, 3]` or `[0; 16]`
+#[derive(Debug, Clone, PartialEq)]
+pub enum ArrayInitializer {
+    List(Vec<Expr>),
+    /// `value` repeated `len` times
+    Repeat {
+        value: Box<Expr>,
+        len: usize,
+    },
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct DefaultedInitializer {
     pub slice: StringSlice,
@@ -169,9 +189,30 @@ pub enum UnaryOp {
     Reference, // ref
     Pointer,   // &
     Deref,     // *
+    Increment, // ++
+    Decrement, // --
 
-    Coalesce, // ?
-    Cascade,  // !
+    Coalesce,      // ?
+    Cascade,       // !
+    PostIncrement, // ++
+    PostDecrement, // --
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AssignOp {
+    Assign, // =
+    Add,    // +=
+    Sub,    // -=
+    Mul,    // *=
+    Div,    // /=
+    Rem,    // %=
+    BitAnd, // &=
+    BitOr,  // |=
+    BitXor, // ^=
+    /// `a ~= b` clears the bits of `b` from `a`, like `a = a & ~b`
+    BitNot,
+    Shl, // <<=
+    Shr, // >>=
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -207,11 +248,95 @@ pub enum BinOp {
     BoolXor,
 }
 
+impl Display for AccessKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let symbol = match self {
+            Self::Value => ".",
+            Self::ValueCoalesce => "?.",
+            Self::ValueCascade => "!.",
+            Self::Reference => "->",
+            Self::ReferenceCoalesce => "?->",
+            Self::ReferenceCascade => "!->",
+        };
+        return write!(f, "{symbol}");
+    }
+}
+
+impl Display for UnaryOp {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let symbol = match self {
+            Self::Add => "+",
+            Self::Sub => "-",
+            Self::BoolNot => "!",
+            Self::BitNot => "~",
+            Self::Reference => "ref",
+            Self::Pointer => "&",
+            Self::Deref => "*",
+            Self::Increment | Self::PostIncrement => "++",
+            Self::Decrement | Self::PostDecrement => "--",
+            Self::Coalesce => "?",
+            Self::Cascade => "!",
+        };
+        return write!(f, "{symbol}");
+    }
+}
+
+impl Display for AssignOp {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let symbol = match self {
+            Self::Assign => "=",
+            Self::Add => "+=",
+            Self::Sub => "-=",
+            Self::Mul => "*=",
+            Self::Div => "/=",
+            Self::Rem => "%=",
+            Self::BitAnd => "&=",
+            Self::BitOr => "|=",
+            Self::BitXor => "^=",
+            Self::BitNot => "~=",
+            Self::Shl => "<<=",
+            Self::Shr => ">>=",
+        };
+        return write!(f, "{symbol}");
+    }
+}
+
+impl Display for BinOp {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let symbol = match self {
+            Self::Mul => "*",
+            Self::Div => "/",
+            Self::Rem => "%",
+            Self::Add => "+",
+            Self::Sub => "-",
+            Self::Shr => ">>",
+            Self::Shl => "<<",
+            Self::Equal => "==",
+            Self::NotEqual => "!=",
+            Self::GreaterEqual => ">=",
+            Self::LessEqual => "<=",
+            Self::Greater => ">",
+            Self::Less => "<",
+            Self::BitAnd => "&",
+            Self::BitOr => "|",
+            Self::BitXor => "^",
+            Self::Range => "..",
+            Self::RangeFromTo => "<..=",
+            Self::RangeTo => "..=",
+            Self::RangeFrom => "<..",
+            Self::BoolAnd => "&&",
+            Self::BoolOr => "||",
+            Self::BoolXor => "^^",
+        };
+        return write!(f, "{symbol}");
+    }
+}
+
 impl AccessKind {
     pub fn try_parse(kind: TokenKind) -> Option<Self> {
         let kind = match kind {
             TokenKind::Symbol(Symbol::Dot) => Self::Value,
-            TokenKind::Symbol(Symbol::ValueCoalesce) => Self::ValueCascade,
+            TokenKind::Symbol(Symbol::ValueCoalesce) => Self::ValueCoalesce,
             TokenKind::Symbol(Symbol::ValueCascade) => Self::ValueCascade,
             TokenKind::Symbol(Symbol::SmallArrow) => Self::Reference,
             TokenKind::Symbol(Symbol::ReferenceCascade) => Self::ReferenceCascade,
@@ -233,6 +358,8 @@ impl UnaryOp {
             TokenKind::Symbol(Symbol::BitAnd) => Self::Pointer,
             TokenKind::Symbol(Symbol::Mul) => Self::Deref,
             TokenKind::Keyword(Keyword::Ref) => Self::Reference,
+            TokenKind::Symbol(Symbol::Increment) => Self::Increment,
+            TokenKind::Symbol(Symbol::Decrement) => Self::Decrement,
             _ => return None,
         };
 
@@ -243,6 +370,8 @@ impl UnaryOp {
         let op = match kind {
             TokenKind::Symbol(Symbol::Optional) => Self::Coalesce,
             TokenKind::Symbol(Symbol::BoolNot) => Self::Cascade,
+            TokenKind::Symbol(Symbol::Increment) => Self::PostIncrement,
+            TokenKind::Symbol(Symbol::Decrement) => Self::PostDecrement,
             _ => return None,
         };
 
@@ -250,6 +379,48 @@ impl UnaryOp {
     }
 }
 
+impl AssignOp {
+    pub fn try_parse(kind: TokenKind) -> Option<Self> {
+        let op = match kind {
+            TokenKind::Symbol(Symbol::Assign) => Self::Assign,
+            TokenKind::Symbol(Symbol::AddAssign) => Self::Add,
+            TokenKind::Symbol(Symbol::SubAssign) => Self::Sub,
+            TokenKind::Symbol(Symbol::MulAssign) => Self::Mul,
+            TokenKind::Symbol(Symbol::DivAssign) => Self::Div,
+            TokenKind::Symbol(Symbol::RemAssign) => Self::Rem,
+            TokenKind::Symbol(Symbol::BitAndAssign) => Self::BitAnd,
+            TokenKind::Symbol(Symbol::BitOrAssign) => Self::BitOr,
+            TokenKind::Symbol(Symbol::BitXorAssign) => Self::BitXor,
+            TokenKind::Symbol(Symbol::BitNotAssign) => Self::BitNot,
+            TokenKind::Symbol(Symbol::ShlAssign) => Self::Shl,
+            TokenKind::Symbol(Symbol::ShrAssign) => Self::Shr,
+            _ => return None,
+        };
+
+        return Some(op);
+    }
+
+    /// The operator a compound assignment applies, with the same operand rules, and whether the
+    /// value is complemented first like the `b` in `a ~= b`
+    pub fn bin_op(self) -> Option<(BinOp, bool)> {
+        let op = match self {
+            Self::Assign => return None,
+            Self::BitNot => return Some((BinOp::BitAnd, true)),
+            Self::Add => BinOp::Add,
+            Self::Sub => BinOp::Sub,
+            Self::Mul => BinOp::Mul,
+            Self::Div => BinOp::Div,
+            Self::Rem => BinOp::Rem,
+            Self::BitAnd => BinOp::BitAnd,
+            Self::BitOr => BinOp::BitOr,
+            Self::BitXor => BinOp::BitXor,
+            Self::Shl => BinOp::Shl,
+            Self::Shr => BinOp::Shr,
+        };
+        return Some((op, false));
+    }
+}
+
 impl BinOp {
     pub fn binding(self) -> (usize, usize) {
         match self {
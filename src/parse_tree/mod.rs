@@ -1,6 +1,6 @@
 use std::{fmt::Debug, sync::Arc};
 
-use decl::{DeclLvl1, DeclModifier, ImportDecl, NamespaceDecl};
+use decl::{DeclLvl1, DeclModifier, ImportDecl, NamespaceDecl, UsingDecl};
 use parse::error::ParserError;
 
 use crate::{
@@ -23,7 +23,7 @@ pub struct ParseTree {
     pub slice: StringSlice,
     pub imports: Vec<ImportDecl>,
     pub namespace: Option<NamespaceDecl>,
-    pub usings: Vec<NamespaceDecl>,
+    pub usings: Vec<UsingDecl>,
     pub body: Vec<DeclModifier<DeclLvl1>>,
 }
 
@@ -3,11 +3,12 @@ use std::fmt::Debug;
 use crate::{
     parse_tree::{
         decl::{
-            ClauseKind, DeclLvl1, DeclLvl1Kind, DeclLvl2, DeclLvl2Kind, DeclModifier, EnumDecl,
-            EnumDeclKind, FuncBody, FuncBodyKind, FuncParam, FunctionDecl, GenericType,
-            GenericsDecl, ImplDecl, ImportDecl, IntEnumBody, IntEnumParam, IntEnumType,
-            NamespaceDecl, StructBody, StructDecl, StructDeclKind, StructParam, ThisParam,
-            TraitBody, TraitDecl, TypeClause, UnionDecl, VariableDecl,
+            Attr, AttrParam, AttrParamKind, Attrs, ClauseKind, DeclLvl1, DeclLvl1Kind, DeclLvl2,
+            DeclLvl2Kind, DeclModifier, EnumDecl, EnumDeclKind, FuncBody, FuncBodyKind, FuncParam,
+            FunctionDecl, GenericType, GenericsDecl, ImplDecl, ImportDecl, IntEnumBody,
+            IntEnumParam, IntEnumType, NamespaceDecl, StructBody, StructDecl, StructDeclKind,
+            StructParam, ThisParam, TraitBody, TraitDecl, TypeClause, UnionDecl, UsingDecl,
+            VariableDecl,
         },
         statement::{FunctionModifier, VariableModifier, VariableName},
         types::{RefKind, Type},
@@ -20,7 +21,12 @@ use crate::{
     },
 };
 
-use super::{error::ParserError, expr::parse_expr, statement::parse_block, types::parse_type};
+use super::{
+    error::{Expected, ParserError},
+    expr::{parse_expr, parse_primitive},
+    statement::parse_block,
+    types::parse_type,
+};
 
 pub fn parse_lvl_1_decl(
     tokenizer: &mut Tokenizer,
@@ -111,6 +117,7 @@ where
     let peek = tokenizer.peek(0)?;
     let start = peek.slice;
 
+    let attrs = parse_attrs(tokenizer)?;
     let generics = parse_generics_decl(tokenizer)?;
 
     let peek = tokenizer.peek(0)?;
@@ -124,14 +131,19 @@ where
 
     let peek = tokenizer.peek(0)?;
     let Some(value) = get(tokenizer)? else {
-        if generics.is_some() || is_pub {
-            return Err(ParserError::unexpected_token(peek));
+        if attrs.is_some() || generics.is_some() || is_pub {
+            return Err(ParserError::expected(
+                peek,
+                &[Expected::Decl],
+                "after modifiers",
+            ));
         }
         return Ok(None);
     };
 
     return Ok(Some(DeclModifier {
         slice: start.merge(&slice(&value)),
+        attrs,
         generics,
         is_pub,
         value,
@@ -148,12 +160,20 @@ pub fn parse_import(tokenizer: &mut Tokenizer) -> Result<Option<ImportDecl>, Par
 
     let next = tokenizer.next()?;
     let TokenKind::String(path) = next.kind else {
-        return Err(ParserError::unexpected_token(next));
+        return Err(ParserError::expected(
+            next,
+            &[Expected::String],
+            "after `import`",
+        ));
     };
 
     let next = tokenizer.next()?;
     let TokenKind::Symbol(Symbol::Semicolon) = next.kind else {
-        return Err(ParserError::unexpected_token(next));
+        return Err(ParserError::expected(
+            next,
+            &[Expected::Symbol(Symbol::Semicolon)],
+            "after an import",
+        ));
     };
 
     return Ok(Some(ImportDecl {
@@ -162,7 +182,7 @@ pub fn parse_import(tokenizer: &mut Tokenizer) -> Result<Option<ImportDecl>, Par
     }));
 }
 
-pub fn parse_using(tokenizer: &mut Tokenizer) -> Result<Option<NamespaceDecl>, ParserError> {
+pub fn parse_using(tokenizer: &mut Tokenizer) -> Result<Option<UsingDecl>, ParserError> {
     let peek = tokenizer.peek(0)?;
     let TokenKind::Keyword(Keyword::Using) = peek.kind else {
         return Ok(None);
@@ -172,17 +192,43 @@ pub fn parse_using(tokenizer: &mut Tokenizer) -> Result<Option<NamespaceDecl>, P
 
     let peek = tokenizer.peek(0)?;
     let Some(path) = IdentPath::try_parse(tokenizer)? else {
-        return Err(ParserError::unexpected_token(peek));
+        return Err(ParserError::expected(
+            peek,
+            &[Expected::Identifier],
+            "after `using`",
+        ));
+    };
+
+    let peek = tokenizer.peek(0)?;
+    let alias = if let TokenKind::Symbol(Symbol::Assign) = peek.kind {
+        tokenizer.next()?;
+
+        let next = tokenizer.next()?;
+        let TokenKind::Identifier(alias) = next.kind else {
+            return Err(ParserError::expected(
+                next,
+                &[Expected::Identifier],
+                "after `=` in a using",
+            ));
+        };
+        Some(alias)
+    } else {
+        None
     };
 
     let next = tokenizer.next()?;
     let TokenKind::Symbol(Symbol::Semicolon) = next.kind else {
-        return Err(ParserError::unexpected_token(next));
+        return Err(ParserError::expected(
+            next,
+            &[Expected::Symbol(Symbol::Semicolon)],
+            "after a using",
+        ));
     };
 
-    return Ok(Some(NamespaceDecl {
+    return Ok(Some(UsingDecl {
         slice: start.merge(&next.slice),
         path,
+        alias,
     }));
 }
 
@@ -196,12 +242,20 @@ pub fn parse_namespace(tokenizer: &mut Tokenizer) -> Result<Option<NamespaceDecl
 
     let peek = tokenizer.peek(0)?;
     let Some(path) = IdentPath::try_parse(tokenizer)? else {
-        return Err(ParserError::unexpected_token(peek));
+        return Err(ParserError::expected(
+            peek,
+            &[Expected::Identifier],
+            "after `namespace`",
+        ));
     };
 
     let next = tokenizer.next()?;
     let TokenKind::Symbol(Symbol::Semicolon) = next.kind else {
-        return Err(ParserError::unexpected_token(next));
+        return Err(ParserError::expected(
+            next,
+            &[Expected::Symbol(Symbol::Semicolon)],
+            "after a namespace",
+        ));
     };
 
     return Ok(Some(NamespaceDecl {
@@ -222,7 +276,11 @@ pub fn parse_impl_decl(tokenizer: &mut Tokenizer) -> Result<Option<ImplDecl>, Pa
 
     let next = tokenizer.next()?;
     let TokenKind::Keyword(Keyword::For) = next.kind else {
-        return Err(ParserError::unexpected_token(next));
+        return Err(ParserError::expected(
+            next,
+            &[Expected::Keyword(Keyword::For)],
+            "after the implemented trait",
+        ));
     };
 
     let ty = parse_type(tokenizer)?;
@@ -246,8 +304,26 @@ pub fn parse_trait_decl(tokenizer: &mut Tokenizer) -> Result<Option<TraitDecl>,
     let start = peek.slice;
 
     let next = tokenizer.next()?;
-    let TokenKind::Identifier(name) = next.kind else {
-        return Err(ParserError::unexpected_token(next));
+    let (name, operator) = match next.kind {
+        TokenKind::Identifier(name) => (name, None),
+        TokenKind::Keyword(Keyword::Operator) => {
+            let next = tokenizer.next()?;
+            let TokenKind::OperatorTrait(op) = next.kind else {
+                return Err(ParserError::expected(
+                    next,
+                    &[Expected::OperatorTrait],
+                    "after `operator`",
+                ));
+            };
+            (op.as_str().into(), Some(op))
+        }
+        _ => {
+            return Err(ParserError::expected(
+                next,
+                &[Expected::Identifier, Expected::Keyword(Keyword::Operator)],
+                "after `trait`",
+            ))
+        }
     };
 
     let body = parse_trait_body(tokenizer)?;
@@ -255,6 +331,7 @@ pub fn parse_trait_decl(tokenizer: &mut Tokenizer) -> Result<Option<TraitDecl>,
     return Ok(Some(TraitDecl {
         slice: start.merge(&body.slice),
         name,
+        operator,
         body,
     }));
 }
@@ -269,7 +346,11 @@ pub fn parse_union_decl(tokenizer: &mut Tokenizer) -> Result<Option<UnionDecl>,
 
     let next = tokenizer.next()?;
     let TokenKind::Identifier(name) = next.kind else {
-        return Err(ParserError::unexpected_token(next));
+        return Err(ParserError::expected(
+            next,
+            &[Expected::Identifier],
+            "after `union`",
+        ));
     };
 
     let body = parse_struct_body(tokenizer)?;
@@ -291,7 +372,11 @@ pub fn parse_enum_decl(tokenizer: &mut Tokenizer) -> Result<Option<EnumDecl>, Pa
 
     let next = tokenizer.next()?;
     let TokenKind::Identifier(name) = next.kind else {
-        return Err(ParserError::unexpected_token(next));
+        return Err(ParserError::expected(
+            next,
+            &[Expected::Identifier],
+            "after `enum`",
+        ));
     };
 
     let peek = tokenizer.peek(0)?;
@@ -300,7 +385,11 @@ pub fn parse_enum_decl(tokenizer: &mut Tokenizer) -> Result<Option<EnumDecl>, Pa
         tokenizer.next()?;
         let next = tokenizer.next()?;
         let Some(ty) = IntEnumType::from(next.kind.clone()) else {
-            return Err(ParserError::unexpected_token(next));
+            return Err(ParserError::expected(
+                next,
+                &[Expected::IntegerType],
+                "after `:` in an enum",
+            ));
         };
 
         let body = parse_int_enum_body(tokenizer)?;
@@ -331,13 +420,21 @@ pub fn parse_struct_decl(tokenizer: &mut Tokenizer) -> Result<Option<StructDecl>
 
     let next = tokenizer.next()?;
     let TokenKind::Identifier(name) = next.kind else {
-        return Err(ParserError::unexpected_token(next));
+        return Err(ParserError::expected(
+            next,
+            &[Expected::Identifier],
+            "after `struct`",
+        ));
     };
 
     if let Some(ty) = parse_type_annotation(tokenizer)? {
         let next = tokenizer.next()?;
         let TokenKind::Symbol(Symbol::Semicolon) = next.kind else {
-            return Err(ParserError::unexpected_token(next));
+            return Err(ParserError::expected(
+                next,
+                &[Expected::Symbol(Symbol::Semicolon)],
+                "after a wrapper struct",
+            ));
         };
         return Ok(Some(StructDecl {
             slice: start.merge(&next.slice),
@@ -377,12 +474,20 @@ pub fn parse_func_decl(tokenizer: &mut Tokenizer) -> Result<Option<FunctionDecl>
     let ident = tokenizer.next()?;
 
     let TokenKind::Identifier(name) = ident.kind else {
-        return Err(ParserError::unexpected_token(ident));
+        return Err(ParserError::expected(
+            ident,
+            &[Expected::Identifier],
+            "after `func`",
+        ));
     };
 
     let next = tokenizer.next()?;
     let TokenKind::Symbol(Symbol::ParenOpen) = next.kind else {
-        return Err(ParserError::unexpected_token(next));
+        return Err(ParserError::expected(
+            next,
+            &[Expected::Symbol(Symbol::ParenOpen)],
+            "after the function name",
+        ));
     };
 
     let this_param = parse_this_param(tokenizer)?;
@@ -416,13 +521,21 @@ pub fn parse_func_decl(tokenizer: &mut Tokenizer) -> Result<Option<FunctionDecl>
             let next = tokenizer.next()?;
 
             let TokenKind::Identifier(name) = next.kind else {
-                return Err(ParserError::unexpected_token(next));
+                return Err(ParserError::expected(
+                    next,
+                    &[Expected::Identifier],
+                    "in function parameters",
+                ));
             };
 
             let next = tokenizer.next()?;
 
             let TokenKind::Symbol(Symbol::Colon) = next.kind else {
-                return Err(ParserError::unexpected_token(next));
+                return Err(ParserError::expected(
+                    next,
+                    &[Expected::Symbol(Symbol::Colon)],
+                    "after a parameter name",
+                ));
             };
 
             let ty = parse_type(tokenizer)?;
@@ -444,7 +557,16 @@ pub fn parse_func_decl(tokenizer: &mut Tokenizer) -> Result<Option<FunctionDecl>
                     }
                 }
                 TokenKind::Symbol(Symbol::ParenClose) => break,
-                _ => return Err(ParserError::unexpected_token(next)),
+                _ => {
+                    return Err(ParserError::expected(
+                        next,
+                        &[
+                            Expected::Symbol(Symbol::Comma),
+                            Expected::Symbol(Symbol::ParenClose),
+                        ],
+                        "after a parameter",
+                    ))
+                }
             }
         },
     }
@@ -463,7 +585,17 @@ pub fn parse_func_decl(tokenizer: &mut Tokenizer) -> Result<Option<FunctionDecl>
             tokenizer.next()?;
             (None, peek.slice)
         }
-        _ => return Err(ParserError::unexpected_token(peek)),
+        _ => {
+            return Err(ParserError::expected(
+                peek,
+                &[
+                    Expected::Symbol(Symbol::Semicolon),
+                    Expected::Symbol(Symbol::WideArrow),
+                    Expected::Symbol(Symbol::BraceOpen),
+                ],
+                "after function signature",
+            ))
+        }
     };
 
     return Ok(Some(FunctionDecl {
@@ -474,10 +606,42 @@ pub fn parse_func_decl(tokenizer: &mut Tokenizer) -> Result<Option<FunctionDecl>
         params,
         ret,
         body,
+        linkage: None,
+        sentinel: None,
     }));
 }
 
 pub fn parse_var_decl(tokenizer: &mut Tokenizer) -> Result<Option<VariableDecl>, ParserError> {
+    let Some(decl) = parse_var_decl_unterminated(tokenizer)? else {
+        return Ok(None);
+    };
+
+    let next = tokenizer.next()?;
+    let TokenKind::Symbol(Symbol::Semicolon) = next.kind else {
+        let expected: &[Expected] = match decl.init {
+            Some(_) => &[Expected::Symbol(Symbol::Semicolon)],
+            None => &[
+                Expected::Symbol(Symbol::Assign),
+                Expected::Symbol(Symbol::Semicolon),
+            ],
+        };
+        return Err(ParserError::expected(
+            next,
+            expected,
+            "after a variable declaration",
+        ));
+    };
+
+    return Ok(Some(VariableDecl {
+        slice: decl.slice.merge(&next.slice),
+        ..decl
+    }));
+}
+
+/// A variable declaration without its `;`, like the one starting a `for` loop
+pub fn parse_var_decl_unterminated(
+    tokenizer: &mut Tokenizer,
+) -> Result<Option<VariableDecl>, ParserError> {
     let peek = tokenizer.peek(0)?;
 
     let modifier = match peek.kind {
@@ -507,156 +671,210 @@ pub fn parse_var_decl(tokenizer: &mut Tokenizer) -> Result<Option<VariableDecl>,
     let name = match ident.kind {
         TokenKind::Identifier(ident) => VariableName::Identifier(ident),
         TokenKind::Keyword(Keyword::Discard) => VariableName::Discard,
-        _ => return Err(ParserError::unexpected_token(ident)),
+        _ => {
+            return Err(ParserError::expected(
+                ident,
+                &[Expected::Identifier, Expected::Keyword(Keyword::Discard)],
+                "in a variable declaration",
+            ))
+        }
     };
 
     let ty = parse_type_annotation(tokenizer)?;
 
-    let next = tokenizer.next()?;
-
-    match next.kind {
+    let peek = tokenizer.peek(0)?;
+    let init = match peek.kind {
         TokenKind::Symbol(Symbol::Assign) => {
+            tokenizer.next()?;
+
             let peek = tokenizer.peek(0)?;
             let Some(expr) = parse_expr(tokenizer)? else {
-                return Err(ParserError::unexpected_token(peek));
+                return Err(ParserError::expected(peek, &[Expected::Expr], "after `=`"));
             };
+            Some(expr)
+        }
+        _ => None,
+    };
+
+    let end = match (&init, &ty) {
+        (Some(init), _) => &init.slice,
+        (None, Some(ty)) => &ty.slice,
+        (None, None) => &ident.slice,
+    };
+
+    return Ok(Some(VariableDecl {
+        slice: start.merge(end),
+        modifier,
+        name,
+        ty,
+        init,
+    }));
+}
+
+/// Any number of `#[...]`, merged into one list
+pub fn parse_attrs(tokenizer: &mut Tokenizer) -> Result<Option<Attrs>, ParserError> {
+    let peek = tokenizer.peek(0)?;
+    let TokenKind::Symbol(Symbol::Pound) = peek.kind else {
+        return Ok(None);
+    };
+    let start = peek.slice;
+
+    let mut attrs = vec![];
+    let mut last = start.clone();
+
+    while let TokenKind::Symbol(Symbol::Pound) = tokenizer.peek(0)?.kind {
+        tokenizer.next()?;
+
+        let next = tokenizer.next()?;
+        let TokenKind::Symbol(Symbol::BracketOpen) = next.kind else {
+            return Err(ParserError::expected(
+                next,
+                &[Expected::Symbol(Symbol::BracketOpen)],
+                "after `#`",
+            ));
+        };
+
+        loop {
+            let attr = parse_attr(tokenizer)?;
+            attrs.push(attr);
 
             let next = tokenizer.next()?;
-            let TokenKind::Symbol(Symbol::Semicolon) = next.kind else {
-                return Err(ParserError::unexpected_token(next));
-            };
+            match next.kind {
+                TokenKind::Symbol(Symbol::Comma) => (),
+                TokenKind::Symbol(Symbol::BracketClose) => {
+                    last = next.slice;
+                    break;
+                }
+                _ => {
+                    return Err(ParserError::expected(
+                        next,
+                        &[
+                            Expected::Symbol(Symbol::Comma),
+                            Expected::Symbol(Symbol::BracketClose),
+                        ],
+                        "after an attribute",
+                    ))
+                }
+            }
+        }
+    }
 
-            return Ok(Some(VariableDecl {
-                slice: start.merge(&next.slice),
-                modifier,
+    return Ok(Some(Attrs {
+        slice: start.merge(&last),
+        attrs,
+    }));
+}
+
+fn parse_attr(tokenizer: &mut Tokenizer) -> Result<Attr, ParserError> {
+    let peek = tokenizer.peek(0)?;
+    let Some(name) = IdentPath::try_parse(tokenizer)? else {
+        return Err(ParserError::expected(
+            peek,
+            &[Expected::Identifier],
+            "for the attribute name",
+        ));
+    };
+
+    return parse_attr_params(tokenizer, name);
+}
+
+/// The `(...)` after an attribute name, if there is one
+fn parse_attr_params(tokenizer: &mut Tokenizer, name: IdentPath) -> Result<Attr, ParserError> {
+    let peek = tokenizer.peek(0)?;
+    let TokenKind::Symbol(Symbol::ParenOpen) = peek.kind else {
+        return Ok(Attr {
+            slice: name.slice.clone(),
+            name,
+            params: vec![],
+        });
+    };
+    tokenizer.next()?;
+
+    let mut params = vec![];
+
+    loop {
+        let peek = tokenizer.peek(0)?;
+        if let TokenKind::Symbol(Symbol::ParenClose) = peek.kind {
+            tokenizer.next()?;
+            return Ok(Attr {
+                slice: name.slice.merge(&peek.slice),
                 name,
-                ty,
-                init: Some(expr),
-            }));
+                params,
+            });
         }
-        TokenKind::Symbol(Symbol::Semicolon) => {
-            return Ok(Some(VariableDecl {
-                slice: start.merge(&next.slice),
-                modifier,
-                name,
-                ty,
-                init: None,
-            }))
+
+        params.push(parse_attr_param(tokenizer)?);
+
+        let next = tokenizer.next()?;
+        match next.kind {
+            TokenKind::Symbol(Symbol::Comma) => (),
+            TokenKind::Symbol(Symbol::ParenClose) => {
+                return Ok(Attr {
+                    slice: name.slice.merge(&next.slice),
+                    name,
+                    params,
+                })
+            }
+            _ => {
+                return Err(ParserError::expected(
+                    next,
+                    &[
+                        Expected::Symbol(Symbol::Comma),
+                        Expected::Symbol(Symbol::ParenClose),
+                    ],
+                    "after an attribute param",
+                ))
+            }
         }
-        _ => return Err(ParserError::unexpected_token(next)),
-    };
+    }
 }
 
-// fn parse_attrs(tokenizer: &mut Tokenizer) -> Result<Option<Attrs>, ParserError> {
-//     let peek = tokenizer.peek(0)?;
-//     let TokenKind::Symbol(Symbol::Pound) = peek.kind else {
-//         return Ok(None);
-//     };
-//     let start = peek.slice;
-//     tokenizer.next()?;
-
-//     let next = tokenizer.next()?;
-//     let TokenKind::Symbol(Symbol::BracketOpen) = next.kind else {
-//         return Err(ParserError::unexpected_token(next));
-//     };
-
-//     let mut attrs = vec![];
-//     let mut last;
-
-//     loop {
-//         let attr = parse_attr(tokenizer)?;
-//         attrs.push(attr);
-
-//         let next = tokenizer.next()?;
-//         last = next.slice.clone();
-//         match next.kind {
-//             TokenKind::Symbol(Symbol::Comma) => (),
-//             TokenKind::Symbol(Symbol::BracketClose) => break,
-//             _ => return Err(ParserError::unexpected_token(next)),
-//         }
-//     }
-
-//     return Ok(Some(Attrs {
-//         slice: start.merge(&last),
-//         attrs,
-//     }));
-// }
-
-// fn parse_attr(tokenizer: &mut Tokenizer) -> Result<Attr, ParserError> {
-//     let next = tokenizer.next()?;
-//     let start = next.slice.clone();
-
-//     let TokenKind::Identifier(name) = next.kind else {
-//         return Err(ParserError::unexpected_token(next));
-//     };
-
-//     let peek = tokenizer.peek(0)?;
-//     let TokenKind::Symbol(Symbol::ParenOpen) = peek.kind else {
-//         return Ok(Attr {
-//             slice: start,
-//             name,
-//             params: vec![],
-//         });
-//     };
-//     tokenizer.next()?;
-
-//     let mut params = vec![];
-//     let mut last;
-
-//     loop {
-//         let param = parse_attr_param(tokenizer)?;
-
-//         params.push(param);
-
-//         let next = tokenizer.next()?;
-//         last = next.slice.clone();
-
-//         match next.kind {
-//             TokenKind::Symbol(Symbol::Comma) => (),
-//             TokenKind::Symbol(Symbol::ParenClose) => break,
-//             _ => return Err(ParserError::unexpected_token(next)),
-//         }
-//     }
-
-//     return Ok(Attr {
-//         slice: start.merge(&last),
-//         name,
-//         params,
-//     });
-// }
-
-// fn parse_attr_param(tokenizer: &mut Tokenizer) -> Result<AttrParam, ParserError> {
-//     let peek = tokenizer.peek(0)?;
-//     let start = peek.slice.clone();
-//     let name = if let TokenKind::Identifier(ident) = peek.kind
-//         && tokenizer.peek(1)?.kind == TokenKind::Symbol(Symbol::Assign)
-//     {
-//         tokenizer.next()?;
-//         tokenizer.next()?;
-//         Some(ident)
-//     } else {
-//         None
-//     };
-
-//     if let Some(primitive) = parse_primitive(tokenizer)? {
-//         return Ok(AttrParam {
-//             slice: start.merge(&primitive.slice),
-//             name,
-//             value: AttrParamKind::Value(primitive),
-//         });
-//     }
-
-//     let next = tokenizer.next()?;
-//     let TokenKind::Identifier(ident) = next.kind.clone() else {
-//         return Err(ParserError::unexpected_token(next));
-//     };
-
-//     return Ok(AttrParam {
-//         slice: start.merge(&next.slice),
-//         name,
-//         value: AttrParamKind::Ident(ident),
-//     });
-// }
+fn parse_attr_param(tokenizer: &mut Tokenizer) -> Result<AttrParam, ParserError> {
+    let peek = tokenizer.peek(0)?;
+    let start = peek.slice.clone();
+
+    let name = if let TokenKind::Identifier(ident) = peek.kind
+        && tokenizer.peek(1)?.kind == TokenKind::Symbol(Symbol::Assign)
+    {
+        tokenizer.next()?;
+        tokenizer.next()?;
+        Some(ident)
+    } else {
+        None
+    };
+
+    if let Some(primitive) = parse_primitive(tokenizer)? {
+        return Ok(AttrParam {
+            slice: start.merge(&primitive.slice),
+            name,
+            value: AttrParamKind::Value(primitive),
+        });
+    }
+
+    let peek = tokenizer.peek(0)?;
+    let Some(path) = IdentPath::try_parse(tokenizer)? else {
+        return Err(ParserError::expected(
+            peek,
+            &[Expected::Identifier, Expected::String, Expected::Integer],
+            "for an attribute param",
+        ));
+    };
+
+    if let TokenKind::Symbol(Symbol::ParenOpen) = tokenizer.peek(0)?.kind {
+        let attr = parse_attr_params(tokenizer, path)?;
+        return Ok(AttrParam {
+            slice: start.merge(&attr.slice),
+            name,
+            value: AttrParamKind::Attr(attr),
+        });
+    }
+
+    return Ok(AttrParam {
+        slice: start.merge(&path.slice),
+        name,
+        value: AttrParamKind::Path(path),
+    });
+}
 
 fn parse_generics_decl(tokenizer: &mut Tokenizer) -> Result<Option<GenericsDecl>, ParserError> {
     let peek = tokenizer.peek(0)?;
@@ -700,7 +918,16 @@ fn parse_generic_type(tokenizer: &mut Tokenizer) -> Result<Option<GenericType>,
                 clauses: vec![],
             }))
         }
-        _ => return Err(ParserError::unexpected_token(next)),
+        _ => {
+            return Err(ParserError::expected(
+                next,
+                &[
+                    Expected::Symbol(Symbol::Colon),
+                    Expected::Symbol(Symbol::Semicolon),
+                ],
+                "after a generic type name",
+            ))
+        }
     }
 
     let mut clauses = vec![];
@@ -720,7 +947,16 @@ fn parse_generic_type(tokenizer: &mut Tokenizer) -> Result<Option<GenericType>,
                     clauses,
                 }))
             }
-            _ => return Err(ParserError::unexpected_token(next)),
+            _ => {
+                return Err(ParserError::expected(
+                    next,
+                    &[
+                        Expected::Symbol(Symbol::Comma),
+                        Expected::Symbol(Symbol::Semicolon),
+                    ],
+                    "after a type clause",
+                ))
+            }
         }
     }
 }
@@ -761,7 +997,11 @@ fn parse_type_clause(tokenizer: &mut Tokenizer) -> Result<TypeClause, ParserErro
 pub fn parse_trait_body(tokenizer: &mut Tokenizer) -> Result<TraitBody, ParserError> {
     let next = tokenizer.peek(0)?;
     let TokenKind::Symbol(Symbol::BraceOpen) = next.kind else {
-        return Err(ParserError::unexpected_token(next));
+        return Err(ParserError::expected(
+            next,
+            &[Expected::Symbol(Symbol::BraceOpen)],
+            "to open the body",
+        ));
     };
     tokenizer.next()?;
 
@@ -770,8 +1010,6 @@ pub fn parse_trait_body(tokenizer: &mut Tokenizer) -> Result<TraitBody, ParserEr
     let mut decls = vec![];
 
     if let TokenKind::Symbol(Symbol::BraceClose) = tokenizer.peek(0)?.kind {
-        tokenizer.next()?;
-
         let last = tokenizer.next()?.slice;
 
         return Ok(TraitBody {
@@ -783,14 +1021,16 @@ pub fn parse_trait_body(tokenizer: &mut Tokenizer) -> Result<TraitBody, ParserEr
     loop {
         let peek = tokenizer.peek(0)?;
         let Some(decl) = parse_lvl_2_decl(tokenizer)? else {
-            return Err(ParserError::unexpected_token(peek));
+            return Err(ParserError::expected(
+                peek,
+                &[Expected::Decl, Expected::Symbol(Symbol::BraceClose)],
+                "in a trait body",
+            ));
         };
 
         decls.push(decl);
 
         if let TokenKind::Symbol(Symbol::BraceClose) = tokenizer.peek(0)?.kind {
-            tokenizer.next()?;
-
             let last = tokenizer.next()?.slice;
 
             return Ok(TraitBody {
@@ -804,7 +1044,11 @@ pub fn parse_trait_body(tokenizer: &mut Tokenizer) -> Result<TraitBody, ParserEr
 pub fn parse_int_enum_body(tokenizer: &mut Tokenizer) -> Result<IntEnumBody, ParserError> {
     let next = tokenizer.peek(0)?;
     let TokenKind::Symbol(Symbol::BraceOpen) = next.kind else {
-        return Err(ParserError::unexpected_token(next));
+        return Err(ParserError::expected(
+            next,
+            &[Expected::Symbol(Symbol::BraceOpen)],
+            "to open the body",
+        ));
     };
 
     let start = next.slice;
@@ -825,11 +1069,20 @@ pub fn parse_int_enum_body(tokenizer: &mut Tokenizer) -> Result<IntEnumBody, Par
     loop {
         tokenizer.next()?;
 
+        let attrs = parse_attrs(tokenizer)?;
+
         let next = tokenizer.next()?;
         let TokenKind::Identifier(name) = next.kind else {
-            return Err(ParserError::unexpected_token(next));
+            return Err(ParserError::expected(
+                next,
+                &[Expected::Identifier],
+                "in an enum body",
+            ));
+        };
+        let start = match &attrs {
+            Some(attrs) => attrs.slice.clone(),
+            None => next.slice,
         };
-        let start = next.slice;
 
         let peek = tokenizer.peek(0)?;
         if let TokenKind::Symbol(Symbol::Assign) = peek.kind {
@@ -838,17 +1091,19 @@ pub fn parse_int_enum_body(tokenizer: &mut Tokenizer) -> Result<IntEnumBody, Par
             let peek = tokenizer.peek(0)?;
 
             let Some(expr) = parse_expr(tokenizer)? else {
-                return Err(ParserError::unexpected_token(peek));
+                return Err(ParserError::expected(peek, &[Expected::Expr], "after `=`"));
             };
 
             params.push(IntEnumParam {
                 slice: start.merge(&expr.slice),
+                attrs,
                 name,
                 value: Some(expr),
             });
         } else {
             params.push(IntEnumParam {
                 slice: start,
+                attrs,
                 name,
                 value: None,
             });
@@ -863,7 +1118,16 @@ pub fn parse_int_enum_body(tokenizer: &mut Tokenizer) -> Result<IntEnumBody, Par
                 }
             }
             TokenKind::Symbol(Symbol::BraceClose) => break,
-            _ => return Err(ParserError::unexpected_token(next)),
+            _ => {
+                return Err(ParserError::expected(
+                    next,
+                    &[
+                        Expected::Symbol(Symbol::Comma),
+                        Expected::Symbol(Symbol::BraceClose),
+                    ],
+                    "after an enum variant",
+                ))
+            }
         }
     }
 
@@ -878,7 +1142,11 @@ pub fn parse_int_enum_body(tokenizer: &mut Tokenizer) -> Result<IntEnumBody, Par
 pub fn parse_struct_body(tokenizer: &mut Tokenizer) -> Result<StructBody, ParserError> {
     let next = tokenizer.peek(0)?;
     let TokenKind::Symbol(Symbol::BraceOpen) = next.kind else {
-        return Err(ParserError::unexpected_token(next));
+        return Err(ParserError::expected(
+            next,
+            &[Expected::Symbol(Symbol::BraceOpen)],
+            "to open the body",
+        ));
     };
 
     let start = next.slice;
@@ -898,6 +1166,7 @@ pub fn parse_struct_body(tokenizer: &mut Tokenizer) -> Result<StructBody, Parser
 
     loop {
         tokenizer.next()?;
+        let attrs = parse_attrs(tokenizer)?;
         let peek = tokenizer.peek(0)?;
 
         let is_pub = if let TokenKind::Keyword(Keyword::Pub) = peek.kind {
@@ -909,18 +1178,27 @@ pub fn parse_struct_body(tokenizer: &mut Tokenizer) -> Result<StructBody, Parser
 
         let next = tokenizer.next()?;
         let TokenKind::Identifier(name) = next.kind else {
-            return Err(ParserError::unexpected_token(next));
+            return Err(ParserError::expected(
+                next,
+                &[Expected::Identifier],
+                "in a struct body",
+            ));
         };
 
         let next = tokenizer.next()?;
         let TokenKind::Symbol(Symbol::Colon) = next.kind else {
-            return Err(ParserError::unexpected_token(next));
+            return Err(ParserError::expected(
+                next,
+                &[Expected::Symbol(Symbol::Colon)],
+                "after a field name",
+            ));
         };
 
         let ty = parse_type(tokenizer)?;
 
         params.push(StructParam {
             slice: start.merge(&ty.slice),
+            attrs,
             is_pub,
             name,
             ty,
@@ -935,7 +1213,16 @@ pub fn parse_struct_body(tokenizer: &mut Tokenizer) -> Result<StructBody, Parser
                 }
             }
             TokenKind::Symbol(Symbol::BraceClose) => break,
-            _ => return Err(ParserError::unexpected_token(next)),
+            _ => {
+                return Err(ParserError::expected(
+                    next,
+                    &[
+                        Expected::Symbol(Symbol::Comma),
+                        Expected::Symbol(Symbol::BraceClose),
+                    ],
+                    "after a field",
+                ))
+            }
         }
     }
 
@@ -958,13 +1245,17 @@ pub fn parse_func_body(
             tokenizer.next()?;
             let t = tokenizer.peek(0)?;
             let Some(expr) = parse_expr(tokenizer)? else {
-                return Err(ParserError::unexpected_token(t));
+                return Err(ParserError::expected(t, &[Expected::Expr], "after `=>`"));
             };
 
             if let Some(sym) = end_symbol {
                 let next = tokenizer.next()?;
                 if next.kind != TokenKind::Symbol(sym) {
-                    return Err(ParserError::unexpected_token(next));
+                    return Err(ParserError::expected(
+                        next,
+                        &[Expected::Symbol(sym)],
+                        "after the function body",
+                    ));
                 };
 
                 return Ok(FuncBody {
@@ -981,7 +1272,11 @@ pub fn parse_func_body(
         TokenKind::Symbol(Symbol::BraceOpen) => {
             let t = tokenizer.peek(0)?;
             let Some(block) = parse_block(tokenizer)? else {
-                return Err(ParserError::unexpected_token(t));
+                return Err(ParserError::expected(
+                    t,
+                    &[Expected::Symbol(Symbol::BraceOpen)],
+                    "to open the function body",
+                ));
             };
 
             return Ok(FuncBody {
@@ -989,7 +1284,16 @@ pub fn parse_func_body(
                 kind: FuncBodyKind::Block(block),
             });
         }
-        _ => return Err(ParserError::unexpected_token(peek)),
+        _ => {
+            return Err(ParserError::expected(
+                peek,
+                &[
+                    Expected::Symbol(Symbol::WideArrow),
+                    Expected::Symbol(Symbol::BraceOpen),
+                ],
+                "to start the function body",
+            ))
+        }
     };
 }
 
@@ -1010,7 +1314,11 @@ fn parse_this_param(tokenizer: &mut Tokenizer) -> Result<Option<ThisParam>, Pars
     let next = tokenizer.peek(0)?;
     let TokenKind::Keyword(Keyword::ThisValue) = next.kind else {
         if is_mut || ref_kind.is_some() {
-            return Err(ParserError::unexpected_token(next));
+            return Err(ParserError::expected(
+                next,
+                &[Expected::Keyword(Keyword::ThisValue)],
+                "after `mut` or `ref`",
+            ));
         }
         return Ok(None);
     };
@@ -1,21 +1,59 @@
-use crate::tokenizer::{token::Token, TokenizeError};
+use std::fmt::Display;
+
+use crate::tokenizer::{
+    token::{Keyword, Symbol, Token},
+    TokenizeError,
+};
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum ParserError {
     TokenizeError(TokenizeError),
-    UnexpectedToken {
-        token: Token,
-        throwing_location: String,
-    },
+    /// Boxed so every parser's `Result` stays small
+    UnexpectedToken(Box<UnexpectedToken>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnexpectedToken {
+    pub token: Token,
+    /// Everything that would have been accepted instead of `token`
+    pub expected: Vec<Expected>,
+    /// Where the parser was, like "after function signature"
+    pub context: &'static str,
+    /// The line of the parser that gave up, only kept in debug builds
+    pub throwing_location: Option<String>,
+}
+
+/// A token or construct the parser can accept at some point
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Expected {
+    Symbol(Symbol),
+    Keyword(Keyword),
+    Identifier,
+    String,
+    Integer,
+    IntegerType,
+    Expr,
+    Type,
+    Pattern,
+    OperatorTrait,
+    Statement,
+    Decl,
 }
 
 impl ParserError {
     #[track_caller]
-    pub fn unexpected_token(token: Token) -> Self {
-        return Self::UnexpectedToken {
-            token,
-            throwing_location: format!("{}", std::panic::Location::caller()),
+    pub fn expected(token: Token, expected: &[Expected], context: &'static str) -> Self {
+        let throwing_location = match cfg!(debug_assertions) {
+            true => Some(format!("{}", std::panic::Location::caller())),
+            false => None,
         };
+
+        return Self::UnexpectedToken(Box::new(UnexpectedToken {
+            token,
+            expected: expected.to_vec(),
+            context,
+            throwing_location,
+        }));
     }
 }
 
@@ -24,3 +62,33 @@ impl From<TokenizeError> for ParserError {
         Self::TokenizeError(value)
     }
 }
+
+impl Display for Expected {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return match self {
+            Expected::Symbol(symbol) => write!(f, "`{}`", symbol.as_str()),
+            Expected::Keyword(keyword) => write!(f, "`{}`", keyword.as_str()),
+            Expected::Identifier => write!(f, "an identifier"),
+            Expected::String => write!(f, "a string"),
+            Expected::Integer => write!(f, "an integer"),
+            Expected::IntegerType => write!(f, "an integer type"),
+            Expected::Expr => write!(f, "an expression"),
+            Expected::Type => write!(f, "a type"),
+            Expected::Pattern => write!(f, "a pattern"),
+            Expected::OperatorTrait => write!(f, "an operator trait like `_+_`"),
+            Expected::Statement => write!(f, "a statement"),
+            Expected::Decl => write!(f, "a declaration"),
+        };
+    }
+}
+
+/// Lists the alternatives like "`;`, `,` or `)`"
+pub fn describe_expected(expected: &[Expected]) -> String {
+    let names: Vec<_> = expected.iter().map(|it| it.to_string()).collect();
+
+    return match names.split_last() {
+        None => String::new(),
+        Some((last, [])) => last.clone(),
+        Some((last, rest)) => format!("{} or {last}", rest.join(", ")),
+    };
+}
@@ -1,25 +1,56 @@
 use crate::{
     parse_tree::{
         expr::{
-            AccessKind, BinOp, DefaultedInitializer, Expr, ExprKind, GenericsInstance,
-            InitializerKind, InitializerList, LambdaCapture, LambdaCaptures, LambdaParam,
-            LambdaParams, NamedInitializer, PrimitiveExpr, PrimitiveExprKind, UnaryOp,
+            AccessKind, ArrayInitializer, AssignOp, BinOp, DefaultedInitializer, Expr, ExprKind,
+            GenericsInstance, InitializerKind, InitializerList, LambdaCapture, LambdaCaptures,
+            LambdaParam, LambdaParams, NamedInitializer, PrimitiveExpr, PrimitiveExprKind, UnaryOp,
         },
         parse::types::parse_type,
         IdentPath,
     },
+    string::StringSlice,
     tokenizer::{
-        token::{Keyword, Symbol, TokenKind},
+        token::{Keyword, Number, NumberValue, Symbol, TokenKind},
         Tokenizer,
     },
 };
 
-use super::{decl::parse_func_body, error::ParserError};
+use super::{
+    decl::parse_func_body,
+    error::{Expected, ParserError},
+};
 
 type ExprResult = Result<Option<Expr>, ParserError>;
 
+/// Assignments bind the loosest and to the right, so `a = b = c` assigns `c` to both
 pub fn parse_expr(tokenizer: &mut Tokenizer) -> ExprResult {
-    return parse_operators(tokenizer, 0);
+    let Some(target) = parse_operators(tokenizer, 0)? else {
+        return Ok(None);
+    };
+
+    let peek = tokenizer.peek(0)?;
+    let Some(op) = AssignOp::try_parse(peek.kind) else {
+        return Ok(Some(target));
+    };
+    tokenizer.next()?;
+
+    let peek = tokenizer.peek(0)?;
+    let Some(value) = parse_expr(tokenizer)? else {
+        return Err(ParserError::expected(
+            peek,
+            &[Expected::Expr],
+            "after an assignment",
+        ));
+    };
+
+    return Ok(Some(Expr {
+        slice: target.slice.merge(&value.slice),
+        kind: ExprKind::Assign {
+            target: Box::new(target),
+            op,
+            value: Box::new(value),
+        },
+    }));
 }
 
 /// Pratt parsing!! Yippee!!!!
@@ -41,7 +72,11 @@ fn parse_operators(tokenizer: &mut Tokenizer, binding: usize) -> ExprResult {
         tokenizer.next()?;
 
         let Some(rhs) = parse_operators(tokenizer, rhs_binding)? else {
-            return Err(ParserError::unexpected_token(tokenizer.peek(0)?));
+            return Err(ParserError::expected(
+                tokenizer.peek(0)?,
+                &[Expected::Expr],
+                "after a binary operator",
+            ));
         };
         let slice = expr.slice.merge(&rhs.slice);
 
@@ -148,7 +183,11 @@ fn parse_access_arm(tokenizer: &mut Tokenizer, expr: Expr) -> ExprResult {
         let next = tokenizer.next()?;
 
         let TokenKind::Identifier(ident) = next.kind else {
-            return Err(ParserError::unexpected_token(next));
+            return Err(ParserError::expected(
+                next,
+                &[Expected::Identifier],
+                "after a field access",
+            ));
         };
 
         let generics = parse_generics_instance(tokenizer)?;
@@ -182,7 +221,11 @@ fn parse_access_arm(tokenizer: &mut Tokenizer, expr: Expr) -> ExprResult {
             loop {
                 let t = tokenizer.peek(0)?;
                 let Some(expr) = parse_expr(tokenizer)? else {
-                    return Err(ParserError::unexpected_token(t));
+                    return Err(ParserError::expected(
+                        t,
+                        &[Expected::Expr],
+                        "in call arguments",
+                    ));
                 };
                 exprs.push(expr);
 
@@ -199,11 +242,27 @@ fn parse_access_arm(tokenizer: &mut Tokenizer, expr: Expr) -> ExprResult {
                             },
                         }))
                     }
-                    _ => return Err(ParserError::unexpected_token(peek)),
+                    _ => {
+                        return Err(ParserError::expected(
+                            peek,
+                            &[
+                                Expected::Symbol(Symbol::Comma),
+                                Expected::Symbol(Symbol::ParenClose),
+                            ],
+                            "after a call argument",
+                        ))
+                    }
                 }
 
                 let TokenKind::Symbol(Symbol::Comma | Symbol::ParenClose) = peek.kind else {
-                    return Err(ParserError::unexpected_token(peek));
+                    return Err(ParserError::expected(
+                        peek,
+                        &[
+                            Expected::Symbol(Symbol::Comma),
+                            Expected::Symbol(Symbol::ParenClose),
+                        ],
+                        "after a call argument",
+                    ));
                 };
             }
         }
@@ -224,13 +283,21 @@ fn parse_access_arm(tokenizer: &mut Tokenizer, expr: Expr) -> ExprResult {
     tokenizer.next()?;
 
     let Some(index) = parse_expr(tokenizer)? else {
-        return Err(ParserError::unexpected_token(tokenizer.peek(0)?));
+        return Err(ParserError::expected(
+            tokenizer.peek(0)?,
+            &[Expected::Expr],
+            "after `[`",
+        ));
     };
 
     let next = tokenizer.next()?;
 
     let TokenKind::Symbol(Symbol::BracketClose) = next.kind else {
-        return Err(ParserError::unexpected_token(next));
+        return Err(ParserError::expected(
+            next,
+            &[Expected::Symbol(Symbol::BracketClose)],
+            "after an index",
+        ));
     };
 
     let slice = next.slice;
@@ -244,7 +311,6 @@ fn parse_access_arm(tokenizer: &mut Tokenizer, expr: Expr) -> ExprResult {
     }));
 }
 
-/// TODO: Parse array initializer
 fn parse_atom(tokenizer: &mut Tokenizer) -> ExprResult {
     if let Some(ident) = parse_ident(tokenizer)? {
         return Ok(Some(ident));
@@ -291,7 +357,11 @@ fn parse_atom(tokenizer: &mut Tokenizer) -> ExprResult {
 
                     let next = tokenizer.next()?;
                     let TokenKind::Symbol(Symbol::Greater) = next.kind else {
-                        return Err(ParserError::unexpected_token(next));
+                        return Err(ParserError::expected(
+                            next,
+                            &[Expected::Symbol(Symbol::Greater)],
+                            "after the type in `sizeof`",
+                        ));
                     };
 
                     return Ok(Some(Expr {
@@ -302,12 +372,20 @@ fn parse_atom(tokenizer: &mut Tokenizer) -> ExprResult {
                 TokenKind::Symbol(Symbol::ParenOpen) => {
                     let next = tokenizer.peek(0)?;
                     let Some(expr) = parse_expr(tokenizer)? else {
-                        return Err(ParserError::unexpected_token(next));
+                        return Err(ParserError::expected(
+                            next,
+                            &[Expected::Expr],
+                            "after `sizeof(`",
+                        ));
                     };
 
                     let next = tokenizer.next()?;
                     let TokenKind::Symbol(Symbol::ParenClose) = next.kind else {
-                        return Err(ParserError::unexpected_token(next));
+                        return Err(ParserError::expected(
+                            next,
+                            &[Expected::Symbol(Symbol::ParenClose)],
+                            "after the value in `sizeof`",
+                        ));
                     };
 
                     return Ok(Some(Expr {
@@ -315,20 +393,37 @@ fn parse_atom(tokenizer: &mut Tokenizer) -> ExprResult {
                         kind: ExprKind::SizeofValue(Box::new(expr)),
                     }));
                 }
-                _ => return Err(ParserError::unexpected_token(next)),
+                _ => {
+                    return Err(ParserError::expected(
+                        next,
+                        &[
+                            Expected::Symbol(Symbol::Less),
+                            Expected::Symbol(Symbol::ParenOpen),
+                        ],
+                        "after `sizeof`",
+                    ))
+                }
             }
         }
         TokenKind::Symbol(Symbol::ParenOpen) => {
             tokenizer.next()?;
 
             let Some(expr) = parse_expr(tokenizer)? else {
-                return Err(ParserError::unexpected_token(tokenizer.peek(0)?));
+                return Err(ParserError::expected(
+                    tokenizer.peek(0)?,
+                    &[Expected::Expr],
+                    "after `(`",
+                ));
             };
 
             let next = tokenizer.next()?;
 
             let TokenKind::Symbol(Symbol::ParenClose) = next.kind else {
-                return Err(ParserError::unexpected_token(next));
+                return Err(ParserError::expected(
+                    next,
+                    &[Expected::Symbol(Symbol::ParenClose)],
+                    "to close the parentheses",
+                ));
             };
 
             return Ok(Some(Expr {
@@ -336,6 +431,10 @@ fn parse_atom(tokenizer: &mut Tokenizer) -> ExprResult {
                 ..expr
             }));
         }
+        TokenKind::Symbol(Symbol::BracketOpen) => {
+            tokenizer.next()?;
+            return parse_array(tokenizer, slice).map(Some);
+        }
         _ => {
             return Ok(None);
         }
@@ -346,6 +445,92 @@ fn parse_atom(tokenizer: &mut Tokenizer) -> ExprResult {
     return Ok(Some(Expr { slice, kind }));
 }
 
+/// Everything after the `[` of `[1, 2, 3]` or `[0; 16]`
+fn parse_array(tokenizer: &mut Tokenizer, start: StringSlice) -> Result<Expr, ParserError> {
+    let mut values = vec![];
+
+    loop {
+        let peek = tokenizer.peek(0)?;
+        if let TokenKind::Symbol(Symbol::BracketClose) = peek.kind {
+            tokenizer.next()?;
+            return Ok(Expr {
+                slice: start.merge(&peek.slice),
+                kind: ExprKind::Array(ArrayInitializer::List(values)),
+            });
+        }
+
+        let Some(value) = parse_expr(tokenizer)? else {
+            return Err(ParserError::expected(
+                peek,
+                &[Expected::Expr, Expected::Symbol(Symbol::BracketClose)],
+                "in an array",
+            ));
+        };
+
+        let next = tokenizer.next()?;
+        match next.kind {
+            TokenKind::Symbol(Symbol::Comma) => values.push(value),
+            TokenKind::Symbol(Symbol::BracketClose) => {
+                values.push(value);
+                return Ok(Expr {
+                    slice: start.merge(&next.slice),
+                    kind: ExprKind::Array(ArrayInitializer::List(values)),
+                });
+            }
+            TokenKind::Symbol(Symbol::Semicolon) if values.is_empty() => {
+                let len = tokenizer.next()?;
+                let TokenKind::Number(Number {
+                    text: _,
+                    value: NumberValue::Int(count),
+                    suffix: None | Some(Keyword::Usize),
+                }) = len.kind
+                else {
+                    return Err(ParserError::expected(
+                        len,
+                        &[Expected::Integer],
+                        "for the array length",
+                    ));
+                };
+
+                let next = tokenizer.next()?;
+                let TokenKind::Symbol(Symbol::BracketClose) = next.kind else {
+                    return Err(ParserError::expected(
+                        next,
+                        &[Expected::Symbol(Symbol::BracketClose)],
+                        "after the array length",
+                    ));
+                };
+
+                return Ok(Expr {
+                    slice: start.merge(&next.slice),
+                    kind: ExprKind::Array(ArrayInitializer::Repeat {
+                        value: Box::new(value),
+                        len: count as usize,
+                    }),
+                });
+            }
+            _ => {
+                let expected: &[Expected] = match values.is_empty() {
+                    true => &[
+                        Expected::Symbol(Symbol::Comma),
+                        Expected::Symbol(Symbol::Semicolon),
+                        Expected::Symbol(Symbol::BracketClose),
+                    ],
+                    false => &[
+                        Expected::Symbol(Symbol::Comma),
+                        Expected::Symbol(Symbol::BracketClose),
+                    ],
+                };
+                return Err(ParserError::expected(
+                    next,
+                    expected,
+                    "after an array element",
+                ));
+            }
+        }
+    }
+}
+
 pub fn parse_primitive(tokenizer: &mut Tokenizer) -> Result<Option<PrimitiveExpr>, ParserError> {
     let token = tokenizer.peek(0)?;
 
@@ -355,6 +540,8 @@ pub fn parse_primitive(tokenizer: &mut Tokenizer) -> Result<Option<PrimitiveExpr
         TokenKind::Char(c) => PrimitiveExprKind::Char(c),
         TokenKind::Number(n) => PrimitiveExprKind::Number(n),
         TokenKind::String(s) => PrimitiveExprKind::String(s),
+        TokenKind::ByteString(s) => PrimitiveExprKind::ByteString(s),
+        TokenKind::CString(s) => PrimitiveExprKind::CString(s),
         TokenKind::Keyword(Keyword::Default) => PrimitiveExprKind::Default,
         TokenKind::Keyword(Keyword::True) => PrimitiveExprKind::Bool(true),
         TokenKind::Keyword(Keyword::False) => PrimitiveExprKind::Bool(false),
@@ -407,13 +594,14 @@ pub fn parse_generics_instance(
         return Ok(None);
     };
     let start = peek.slice;
-    tokenizer.next()?;
 
-    let peek = tokenizer.peek(0)?;
+    // Without the `<` the colon starts a cast instead
+    let peek = tokenizer.peek(1)?;
     let TokenKind::Symbol(Symbol::Less) = peek.kind else {
         return Ok(None);
     };
     tokenizer.next()?;
+    tokenizer.next()?;
 
     let mut params = vec![];
 
@@ -443,7 +631,16 @@ pub fn parse_generics_instance(
                         params,
                     }));
                 }
-                _ => return Err(ParserError::unexpected_token(next)),
+                _ => {
+                    return Err(ParserError::expected(
+                        next,
+                        &[
+                            Expected::Symbol(Symbol::Comma),
+                            Expected::Symbol(Symbol::Greater),
+                        ],
+                        "in generic arguments",
+                    ))
+                }
             }
         }
     };
@@ -493,7 +690,17 @@ pub fn parse_initializer_list(
                     TokenKind::Symbol(Symbol::BraceClose | Symbol::Rest) => {
                         break;
                     }
-                    _ => return Err(ParserError::unexpected_token(peek)),
+                    _ => {
+                        return Err(ParserError::expected(
+                            peek,
+                            &[
+                                Expected::Symbol(Symbol::Comma),
+                                Expected::Symbol(Symbol::BraceClose),
+                                Expected::Symbol(Symbol::Rest),
+                            ],
+                            "after a field initializer",
+                        ))
+                    }
                 }
             }
 
@@ -508,7 +715,11 @@ pub fn parse_initializer_list(
 
             let next = tokenizer.next()?;
             let TokenKind::Symbol(Symbol::BraceClose) = next.kind else {
-                return Err(ParserError::unexpected_token(next));
+                return Err(ParserError::expected(
+                    next,
+                    &[Expected::Symbol(Symbol::BraceClose)],
+                    "to close the initializer",
+                ));
             };
 
             return Ok(Some(InitializerList {
@@ -521,7 +732,11 @@ pub fn parse_initializer_list(
             loop {
                 let peek = tokenizer.peek(0)?;
                 let Some(value) = parse_expr(tokenizer)? else {
-                    return Err(ParserError::unexpected_token(peek));
+                    return Err(ParserError::expected(
+                        peek,
+                        &[Expected::Expr],
+                        "in an initializer",
+                    ));
                 };
                 values.push(value);
 
@@ -543,7 +758,16 @@ pub fn parse_initializer_list(
                             }));
                         }
                     }
-                    _ => return Err(ParserError::unexpected_token(next)),
+                    _ => {
+                        return Err(ParserError::expected(
+                            next,
+                            &[
+                                Expected::Symbol(Symbol::Comma),
+                                Expected::Symbol(Symbol::BraceClose),
+                            ],
+                            "after an initializer value",
+                        ))
+                    }
                 }
             }
         }
@@ -562,7 +786,11 @@ fn parse_defaulted_initializer(
 
     let peek = tokenizer.peek(0)?;
     let Some(value) = parse_expr(tokenizer)? else {
-        return Err(ParserError::unexpected_token(peek));
+        return Err(ParserError::expected(
+            peek,
+            &[Expected::Expr],
+            "after `...`",
+        ));
     };
 
     return Ok(Some(DefaultedInitializer {
@@ -574,23 +802,35 @@ fn parse_defaulted_initializer(
 fn parse_named_initializer(tokenizer: &mut Tokenizer) -> Result<NamedInitializer, ParserError> {
     let next = tokenizer.next()?;
     let TokenKind::Symbol(Symbol::Dot) = next.kind else {
-        return Err(ParserError::unexpected_token(next));
+        return Err(ParserError::expected(
+            next,
+            &[Expected::Symbol(Symbol::Dot)],
+            "in a named initializer",
+        ));
     };
     let start = next.slice;
 
     let next = tokenizer.next()?;
     let TokenKind::Identifier(name) = next.kind else {
-        return Err(ParserError::unexpected_token(next));
+        return Err(ParserError::expected(
+            next,
+            &[Expected::Identifier],
+            "after `.`",
+        ));
     };
 
     let next = tokenizer.next()?;
     let TokenKind::Symbol(Symbol::Assign) = next.kind else {
-        return Err(ParserError::unexpected_token(next));
+        return Err(ParserError::expected(
+            next,
+            &[Expected::Symbol(Symbol::Assign)],
+            "after a field name",
+        ));
     };
 
     let peek = tokenizer.peek(0)?;
     let Some(value) = parse_expr(tokenizer)? else {
-        return Err(ParserError::unexpected_token(peek));
+        return Err(ParserError::expected(peek, &[Expected::Expr], "after `=`"));
     };
 
     return Ok(NamedInitializer {
@@ -629,7 +869,16 @@ fn parse_lambda_captures(tokenizer: &mut Tokenizer) -> Result<Option<LambdaCaptu
                     captures,
                 }))
             }
-            _ => return Err(ParserError::unexpected_token(next)),
+            _ => {
+                return Err(ParserError::expected(
+                    next,
+                    &[
+                        Expected::Symbol(Symbol::Comma),
+                        Expected::Symbol(Symbol::BracketClose),
+                    ],
+                    "in lambda captures",
+                ))
+            }
         }
     }
 }
@@ -646,7 +895,11 @@ fn parse_lambda_capture(tokenizer: &mut Tokenizer) -> Result<LambdaCapture, Pars
 
     let next = tokenizer.next()?;
     let TokenKind::Identifier(name) = next.kind else {
-        return Err(ParserError::unexpected_token(next));
+        return Err(ParserError::expected(
+            next,
+            &[Expected::Identifier],
+            "in lambda captures",
+        ));
     };
 
     return Ok(LambdaCapture {
@@ -685,7 +938,16 @@ fn parse_lambda_params(tokenizer: &mut Tokenizer) -> Result<Option<LambdaParams>
                     params,
                 }))
             }
-            _ => return Err(ParserError::unexpected_token(next)),
+            _ => {
+                return Err(ParserError::expected(
+                    next,
+                    &[
+                        Expected::Symbol(Symbol::Comma),
+                        Expected::Symbol(Symbol::ParenClose),
+                    ],
+                    "in lambda parameters",
+                ))
+            }
         }
     }
 }
@@ -702,7 +964,11 @@ fn parse_lambda_param(tokenizer: &mut Tokenizer) -> Result<LambdaParam, ParserEr
 
     let next = tokenizer.next()?;
     let TokenKind::Identifier(name) = next.kind else {
-        return Err(ParserError::unexpected_token(next));
+        return Err(ParserError::expected(
+            next,
+            &[Expected::Identifier],
+            "in lambda parameters",
+        ));
     };
 
     return Ok(LambdaParam {
@@ -718,10 +984,17 @@ mod test {
 
     use crate::{
         parse_tree::{
-            expr::{BinOp, Expr, ExprKind, PrimitiveExpr, PrimitiveExprKind},
+            expr::{
+                AccessKind, ArrayInitializer, AssignOp, BinOp, Expr, ExprKind, PrimitiveExpr,
+                PrimitiveExprKind, UnaryOp,
+            },
             parse::{error::ParserError, expr::parse_expr},
+            types::{Type, TypeKind},
+        },
+        tokenizer::{
+            token::{Number, NumberValue},
+            Tokenizer,
         },
-        tokenizer::{token::Number, Tokenizer},
     };
 
     type TestResult = Result<(), ParserError>;
@@ -740,8 +1013,9 @@ mod test {
                 kind: ExprKind::Primitive(PrimitiveExpr {
                     slice: _,
                     kind: PrimitiveExprKind::Number(Number {
-                        whole: 15,
-                        decimal: 0.0
+                        text: _,
+                        value: NumberValue::Int(15),
+                        suffix: None,
                     })
                 })
             })
@@ -771,8 +1045,9 @@ mod test {
                                         slice: _,
                                         kind:
                                             PrimitiveExprKind::Number(Number {
-                                                whole: 1,
-                                                decimal: 0.0,
+                                                text: _,
+                                                value: NumberValue::Int(1),
+                                                suffix: None,
                                             }),
                                     }),
                             },
@@ -790,8 +1065,9 @@ mod test {
                                                         slice: _,
                                                         kind:
                                                             PrimitiveExprKind::Number(Number {
-                                                                whole: 2,
-                                                                decimal: 0.0,
+                                                                text: _,
+                                                                value: NumberValue::Int(2),
+                                                                suffix: None,
                                                             }),
                                                     }),
                                             },
@@ -804,8 +1080,9 @@ mod test {
                                                         slice: _,
                                                         kind:
                                                             PrimitiveExprKind::Number(Number {
-                                                                whole: 2,
-                                                                decimal: 0.0,
+                                                                text: _,
+                                                                value: NumberValue::Int(2),
+                                                                suffix: None,
                                                             }),
                                                     }),
                                             },
@@ -832,8 +1109,9 @@ mod test {
                 kind: ExprKind::Primitive(PrimitiveExpr {
                     slice: _,
                     kind: PrimitiveExprKind::Number(Number {
-                        whole: 123,
-                        decimal: 0.0,
+                        text: _,
+                        value: NumberValue::Int(123),
+                        suffix: None,
                     })
                 }),
             })
@@ -841,4 +1119,169 @@ mod test {
 
         Ok(())
     }
+
+    #[test]
+    fn assignment() -> TestResult {
+        let mut tokenizer = Tokenizer::new("a = b = arr[j + 1]".into());
+        let tree = parse_expr(&mut tokenizer)?.unwrap();
+
+        let ExprKind::Assign {
+            target,
+            op: AssignOp::Assign,
+            value,
+        } = tree.kind
+        else {
+            panic!("expected an assignment, got {tree:#?}");
+        };
+        assert_matches!(target.kind, ExprKind::Variable { .. });
+        assert_matches!(
+            value.kind,
+            ExprKind::Assign {
+                op: AssignOp::Assign,
+                value: box Expr {
+                    kind: ExprKind::Index { .. },
+                    ..
+                },
+                ..
+            }
+        );
+
+        let ops = [
+            ("+=", AssignOp::Add),
+            ("-=", AssignOp::Sub),
+            ("*=", AssignOp::Mul),
+            ("/=", AssignOp::Div),
+            ("%=", AssignOp::Rem),
+            ("&=", AssignOp::BitAnd),
+            ("|=", AssignOp::BitOr),
+            ("^=", AssignOp::BitXor),
+            ("~=", AssignOp::BitNot),
+            ("<<=", AssignOp::Shl),
+            (">>=", AssignOp::Shr),
+        ];
+        for (symbol, expected) in ops {
+            let src = format!("x.y {symbol} 1 + 2");
+            let tree = parse_expr(&mut Tokenizer::new(src.as_str().into()))?.unwrap();
+
+            let ExprKind::Assign { op, value, .. } = tree.kind else {
+                panic!("expected an assignment for `{symbol}`");
+            };
+            assert_eq!(op, expected);
+            assert_matches!(value.kind, ExprKind::BinOp { op: BinOp::Add, .. });
+        }
+
+        assert!(parse_expr(&mut Tokenizer::new("x = ".into())).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn increments() -> TestResult {
+        let tree = parse_expr(&mut Tokenizer::new("x++".into()))?.unwrap();
+        assert_matches!(
+            tree.kind,
+            ExprKind::UnaryOp {
+                op: UnaryOp::PostIncrement,
+                ..
+            }
+        );
+
+        let tree = parse_expr(&mut Tokenizer::new("--arr[0]".into()))?.unwrap();
+        assert_matches!(
+            tree.kind,
+            ExprKind::UnaryOp {
+                op: UnaryOp::Decrement,
+                value: box Expr {
+                    kind: ExprKind::Index { .. },
+                    ..
+                },
+            }
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn arrays() -> TestResult {
+        let tree = parse_expr(&mut Tokenizer::new("[[1, 2], [3, 4,], []]".into()))?.unwrap();
+        let ExprKind::Array(ArrayInitializer::List(rows)) = &tree.kind else {
+            panic!("expected an array, got {tree:#?}");
+        };
+        let lens: Vec<_> = rows
+            .iter()
+            .map(|it| match &it.kind {
+                ExprKind::Array(ArrayInitializer::List(values)) => values.len(),
+                _ => panic!("expected a nested array"),
+            })
+            .collect();
+        assert_eq!(lens, [2, 2, 0]);
+
+        let tree = parse_expr(&mut Tokenizer::new("[0; 16][1]".into()))?.unwrap();
+        assert_matches!(
+            tree.kind,
+            ExprKind::Index {
+                value: box Expr {
+                    kind: ExprKind::Array(ArrayInitializer::Repeat { len: 16, .. }),
+                    ..
+                },
+                ..
+            }
+        );
+
+        assert_matches!(
+            parse_expr(&mut Tokenizer::new("[1, 2; 3]".into())),
+            Err(ParserError::UnexpectedToken(_))
+        );
+        assert_matches!(
+            parse_expr(&mut Tokenizer::new("[0; n]".into())),
+            Err(ParserError::UnexpectedToken(_))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn value_coalesce() -> TestResult {
+        let tree = parse_expr(&mut Tokenizer::new("a?.b".into()))?;
+
+        assert_matches!(
+            tree,
+            Some(Expr {
+                kind: ExprKind::Field {
+                    access: AccessKind::ValueCoalesce,
+                    ..
+                },
+                ..
+            })
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn cast_after_path() -> TestResult {
+        let tree = parse_expr(&mut Tokenizer::new("a:i32".into()))?;
+
+        assert_matches!(
+            tree,
+            Some(Expr {
+                kind:
+                    ExprKind::Cast {
+                        value:
+                            box Expr {
+                                kind: ExprKind::Variable { .. },
+                                ..
+                            },
+                        ty:
+                            Type {
+                                kind: TypeKind::I32,
+                                ..
+                            },
+                    },
+                ..
+            })
+        );
+
+        Ok(())
+    }
 }
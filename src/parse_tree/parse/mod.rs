@@ -1,7 +1,14 @@
 use decl::{parse_import, parse_lvl_1_decl, parse_namespace, parse_using};
-use error::ParserError;
+use error::{Expected, ParserError, UnexpectedToken};
 
-use crate::tokenizer::{token::TokenKind, Tokenizer};
+use crate::{
+    parse_tree::decl::{DeclLvl1, DeclLvl1Kind, DeclModifier},
+    string::StringSlice,
+    tokenizer::{
+        token::{Keyword, Symbol, Token, TokenKind},
+        TokenizeError, Tokenizer,
+    },
+};
 
 use super::ParseTree;
 
@@ -12,51 +19,497 @@ pub mod pattern;
 pub mod statement;
 pub mod types;
 
+/// Parses a whole file, failing on the first error
 pub fn parse_root(tokenizer: &mut Tokenizer) -> Result<ParseTree, ParserError> {
-    let start = tokenizer.peek(0)?.slice;
+    let (tree, errors) = parse_file(tokenizer);
+
+    return match errors.into_iter().next() {
+        Some(error) => Err(error),
+        None => Ok(tree),
+    };
+}
+
+/// Parses a whole file without stopping at errors. Broken declarations are skipped and left in
+/// the tree as error nodes, so every error in the file can be reported at once
+pub fn parse_file(tokenizer: &mut Tokenizer) -> (ParseTree, Vec<ParserError>) {
+    let Some(start) = peek_recovering(tokenizer).map(|it| it.slice) else {
+        return (empty_tree(tokenizer), std::mem::take(&mut tokenizer.errors));
+    };
+    let mut end = start.clone();
 
     let mut imports = vec![];
-    while let Some(import) = parse_import(tokenizer)? {
-        imports.push(import);
+    loop {
+        match parse_import(tokenizer) {
+            Ok(Some(import)) => imports.push(import),
+            Ok(None) => break,
+            Err(error) => {
+                recover(tokenizer, error, true);
+            }
+        }
     }
 
-    let namespace = parse_namespace(tokenizer)?;
+    let namespace = parse_namespace(tokenizer).unwrap_or_else(|error| {
+        recover(tokenizer, error, true);
+        return None;
+    });
 
     let mut usings = vec![];
-    while let Some(using) = parse_using(tokenizer)? {
-        usings.push(using);
-    }
-
-    let peek = tokenizer.peek(0)?;
-    if let TokenKind::Eof = peek.kind {
-        return Ok(ParseTree {
-            slice: start.merge(&peek.slice),
-            imports,
-            namespace,
-            usings,
-            body: vec![],
-        });
+    loop {
+        match parse_using(tokenizer) {
+            Ok(Some(using)) => usings.push(using),
+            Ok(None) => break,
+            Err(error) => {
+                recover(tokenizer, error, true);
+            }
+        }
     }
 
     let mut body = vec![];
 
-    loop {
-        let peek = tokenizer.peek(0)?;
-        let Some(decl) = parse_lvl_1_decl(tokenizer)? else {
-            return Err(ParserError::unexpected_token(peek));
+    while let Some(peek) = peek_recovering(tokenizer) {
+        end = peek.slice.clone();
+
+        if let TokenKind::Eof = peek.kind {
+            break;
+        }
+
+        let error = match parse_lvl_1_decl(tokenizer) {
+            Ok(Some(decl)) => {
+                body.push(decl);
+                continue;
+            }
+            Ok(None) => ParserError::expected(peek.clone(), &[Expected::Decl], ""),
+            Err(error) => error,
         };
 
-        body.push(decl);
+        let eof = matches!(
+            error,
+            ParserError::TokenizeError(TokenizeError::UnexpectedEof(_))
+        );
+        let skipped = recover(tokenizer, error, true);
 
-        let peek = tokenizer.peek(0)?;
-        if let TokenKind::Eof = peek.kind {
-            return Ok(ParseTree {
-                slice: start.merge(&peek.slice),
-                imports,
-                namespace,
-                usings,
-                body,
-            });
+        let slice = match skipped {
+            Some(skipped) => peek.slice.merge(&skipped),
+            None => peek.slice.clone(),
+        };
+        body.push(DeclModifier {
+            slice: slice.clone(),
+            attrs: None,
+            generics: None,
+            is_pub: false,
+            value: DeclLvl1 {
+                slice,
+                kind: DeclLvl1Kind::Error,
+            },
+        });
+
+        if eof {
+            break;
+        }
+
+        // The error was right at the start of a declaration, which can't be parsed either
+        if let Ok(next) = tokenizer.peek(0)
+            && next.slice == peek.slice
+        {
+            let _ = tokenizer.next();
+        }
+    }
+
+    let tree = ParseTree {
+        slice: start.merge(&end),
+        imports,
+        namespace,
+        usings,
+        body,
+    };
+
+    return (tree, std::mem::take(&mut tokenizer.errors));
+}
+
+fn empty_tree(tokenizer: &mut Tokenizer) -> ParseTree {
+    return ParseTree {
+        slice: tokenizer.empty_slice(),
+        imports: vec![],
+        namespace: None,
+        usings: vec![],
+        body: vec![],
+    };
+}
+
+/// Peeks past tokens that fail to tokenize, `None` once the end of the file can't be reached
+fn peek_recovering(tokenizer: &mut Tokenizer) -> Option<Token> {
+    loop {
+        match tokenizer.peek(0) {
+            Ok(token) => return Some(token),
+            Err(error @ TokenizeError::UnexpectedEof(_)) => {
+                tokenizer.errors.push(error.into());
+                return None;
+            }
+            Err(error) => tokenizer.errors.push(error.into()),
         }
     }
 }
+
+/// Records `error` and skips what's left of the broken statement or declaration.
+/// Returns the last token that was skipped
+pub fn recover(
+    tokenizer: &mut Tokenizer,
+    error: ParserError,
+    top_level: bool,
+) -> Option<StringSlice> {
+    let eof = matches!(
+        error,
+        ParserError::TokenizeError(TokenizeError::UnexpectedEof(_))
+    );
+
+    // An enclosing block failing at the same token isn't worth a second error
+    let repeated = match (&error, tokenizer.errors.last()) {
+        (
+            ParserError::UnexpectedToken(box UnexpectedToken { token, .. }),
+            Some(ParserError::UnexpectedToken(box UnexpectedToken { token: last, .. })),
+        ) => token.slice == last.slice,
+        _ => false,
+    };
+    if !repeated {
+        tokenizer.errors.push(error);
+    }
+
+    if eof {
+        return None;
+    }
+
+    let mut depth = 0usize;
+    let mut skipped = None;
+
+    loop {
+        let token = match tokenizer.peek(0) {
+            Ok(token) => token,
+            // Reported by whoever peeks next
+            Err(TokenizeError::UnexpectedEof(_)) => return skipped,
+            Err(error) => {
+                tokenizer.errors.push(error.into());
+                continue;
+            }
+        };
+
+        match token.kind {
+            TokenKind::Eof => return skipped,
+            // The `}` closing the enclosing block or body
+            TokenKind::Symbol(Symbol::BraceClose) if depth == 0 => {
+                if top_level {
+                    let _ = tokenizer.next();
+                    return Some(token.slice);
+                }
+                return skipped;
+            }
+            TokenKind::Symbol(Symbol::Semicolon) if depth == 0 => {
+                let _ = tokenizer.next();
+                return Some(token.slice);
+            }
+            TokenKind::Keyword(
+                Keyword::Func
+                | Keyword::Struct
+                | Keyword::Enum
+                | Keyword::Union
+                | Keyword::Trait
+                | Keyword::Impl
+                | Keyword::Where
+                | Keyword::Pub,
+            ) if top_level && depth == 0 => return skipped,
+            // Attributes of the next declaration
+            TokenKind::Symbol(Symbol::Pound) if top_level && depth == 0 => return skipped,
+            TokenKind::Symbol(Symbol::BraceOpen | Symbol::ParenOpen | Symbol::BracketOpen) => {
+                depth += 1;
+            }
+            TokenKind::Symbol(Symbol::BraceClose) if depth == 1 => {
+                // A whole block was skipped, like the body of a broken function or if
+                let _ = tokenizer.next();
+                return Some(token.slice);
+            }
+            TokenKind::Symbol(Symbol::BraceClose | Symbol::ParenClose | Symbol::BracketClose) => {
+                depth = depth.saturating_sub(1);
+            }
+            _ => {}
+        }
+
+        let _ = tokenizer.next();
+        skipped = Some(token.slice);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::assert_matches::assert_matches;
+
+    use crate::{
+        parse_tree::{
+            decl::{
+                AttrParamKind, ClauseKind, DeclLvl1Kind, DeclLvl2Kind, EnumDeclKind, FuncBody,
+                FuncBodyKind, StructDeclKind,
+            },
+            expr::PrimitiveExprKind,
+            statement::StatementKind,
+            types::{Type, TypeKind},
+        },
+        tokenizer::{token::OperatorTrait, Tokenizer},
+    };
+
+    use super::{
+        error::{describe_expected, Expected, ParserError, UnexpectedToken},
+        parse_file, parse_root,
+    };
+
+    #[test]
+    fn recovery() {
+        let src = "func A() {
+                let x = ;
+                let y = 1;
+                x + ) y;
+            }
+            struct B { x: i32 }
+            func C(: i32 {
+                return 1;
+            }
+            pub func D() => 2;
+            @
+            func E() { F(1";
+        let (tree, errors) = parse_file(&mut Tokenizer::new(src.into()));
+
+        let found: Vec<_> = errors
+            .iter()
+            .map(|it| match it {
+                ParserError::UnexpectedToken(error) => error.token.slice.value(),
+                ParserError::TokenizeError(error) => format!("{error:?}").into(),
+            })
+            .collect();
+        assert_eq!(
+            found.iter().map(|it| &**it).collect::<Vec<_>>(),
+            vec![";", ")", ":", r#"InvalidChar("@")"#, ""]
+        );
+
+        let kinds: Vec<_> = tree.body.iter().map(|it| &it.value.kind).collect();
+        let [DeclLvl1Kind::Lvl2(a), DeclLvl1Kind::Struct(_), DeclLvl1Kind::Error, DeclLvl1Kind::Lvl2(_), DeclLvl1Kind::Error] =
+            &kinds[..]
+        else {
+            panic!("expected the broken declarations to be error nodes, got {kinds:#?}");
+        };
+
+        let DeclLvl2Kind::Function(a) = &a.kind else {
+            panic!("expected a function");
+        };
+        let Some(FuncBody {
+            kind: FuncBodyKind::Block(block),
+            ..
+        }) = &a.body
+        else {
+            panic!("expected a block body");
+        };
+        let statements: Vec<_> = block.statements.iter().map(|it| &it.kind).collect();
+        assert!(matches!(
+            &statements[..],
+            [
+                StatementKind::Error,
+                StatementKind::Decl(_),
+                StatementKind::Error
+            ]
+        ));
+        assert_eq!(&*block.statements[2].slice.value(), "x + ) y;");
+    }
+
+    #[test]
+    fn expected_tokens() {
+        let error = parse_root(&mut Tokenizer::new("func A(): i32 x".into())).unwrap_err();
+
+        let ParserError::UnexpectedToken(box UnexpectedToken {
+            token,
+            expected,
+            context,
+            ..
+        }) = &error
+        else {
+            panic!("expected an unexpected token, got {error:?}");
+        };
+        assert_eq!(&*token.slice.value(), "x");
+        assert_eq!(
+            format!("{} {context}", describe_expected(expected)),
+            "`;`, `=>` or `{` after function signature"
+        );
+
+        let error = parse_root(&mut Tokenizer::new("struct A { x: [i32, y] }".into())).unwrap_err();
+        assert_matches!(
+            error,
+            ParserError::UnexpectedToken(box UnexpectedToken { expected, .. }) if expected == [Expected::Integer]
+        );
+    }
+
+    #[test]
+    fn attributes() -> Result<(), ParserError> {
+        let src = r#"#[Extern(C, symbol = "glClearColor")]
+            func ClearColor(r: f32, g: f32, b: f32, a: f32);
+
+            #[Flags] #[Derive(Std::Debug, Eq), Cfg(Any(Linux, Windows))]
+            enum WindowFlags : u32 {
+                Fullscreen = 0x1,
+                #[Deprecated("use Fullscreen")]
+                OpenGl = 0x2,
+            }
+
+            struct Point {
+                #[Default(0)]
+                pub x: i32,
+            }"#;
+        let tree = parse_root(&mut Tokenizer::new(src.into()))?;
+
+        let attrs = tree.body[0].attrs.as_ref().unwrap();
+        assert_eq!(
+            &*attrs.slice.value(),
+            r#"#[Extern(C, symbol = "glClearColor")]"#
+        );
+        let params = &attrs.attrs[0].params;
+        assert_matches!(&params[0].value, AttrParamKind::Path(path) if path.path == ["C".into()]);
+        assert_eq!(params[1].name.as_deref(), Some("symbol"));
+        assert_eq!(&*params[1].slice.value(), r#"symbol = "glClearColor""#);
+        assert_matches!(
+            &params[1].value,
+            AttrParamKind::Value(value)
+                if value.kind == PrimitiveExprKind::String("glClearColor".into())
+        );
+
+        // Both `#[...]` are merged into one list
+        let attrs = tree.body[1].attrs.as_ref().unwrap();
+        let names: Vec<_> = attrs
+            .attrs
+            .iter()
+            .map(|it| it.name.path.join("::"))
+            .collect();
+        assert_eq!(names, ["Flags", "Derive", "Cfg"]);
+        assert!(attrs.slice.value().starts_with("#[Flags]"));
+        assert!(attrs.slice.value().ends_with("Windows))]"));
+        assert_matches!(
+            &attrs.attrs[1].params[0].value,
+            AttrParamKind::Path(path) if &*path.slice.value() == "Std::Debug"
+        );
+        assert_matches!(
+            &attrs.attrs[2].params[0].value,
+            AttrParamKind::Attr(attr) if attr.params.len() == 2
+        );
+
+        let DeclLvl1Kind::Enum(en) = &tree.body[1].value.kind else {
+            panic!("expected an enum");
+        };
+        let EnumDeclKind::Int { body, .. } = &en.kind else {
+            panic!("expected an int enum");
+        };
+        assert!(body.params[0].attrs.is_none());
+        assert_eq!(
+            &*body.params[1].slice.value(),
+            "#[Deprecated(\"use Fullscreen\")]\n                OpenGl = 0x2"
+        );
+
+        let DeclLvl1Kind::Struct(st) = &tree.body[2].value.kind else {
+            panic!("expected a struct");
+        };
+        let StructDeclKind::Value(body) = &st.kind else {
+            panic!("expected a struct body");
+        };
+        assert_eq!(
+            &*body.params[0].attrs.as_ref().unwrap().slice.value(),
+            "#[Default(0)]"
+        );
+
+        let error = parse_root(&mut Tokenizer::new("#[Flags]".into())).unwrap_err();
+        assert_matches!(
+            error,
+            ParserError::UnexpectedToken(box UnexpectedToken { expected, .. }) if expected == [Expected::Decl]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn attributes_after_error() {
+        let src = "foo #[Extern(C)] func B();";
+        let (tree, errors) = parse_file(&mut Tokenizer::new(src.into()));
+        assert_eq!(errors.len(), 1);
+
+        let [broken, decl] = &tree.body[..] else {
+            panic!("expected two declarations, got {:#?}", tree.body);
+        };
+        assert_matches!(broken.value.kind, DeclLvl1Kind::Error);
+        assert_eq!(&*decl.attrs.as_ref().unwrap().slice.value(), "#[Extern(C)]");
+    }
+
+    #[test]
+    fn operator_traits() -> Result<(), ParserError> {
+        let src = "where TOk; TErr;
+            trait operator _! {
+                const func IsOk(this): bool;
+                const func UnwrapOk(this): TOk;
+            }
+
+            where T : operator +<T, T>, !i32; U : operator _-_:<U>;
+            func Double(val: T): T => val + val;
+
+            impl operator ~_ for Flags {}";
+        let tree = parse_root(&mut Tokenizer::new(src.into()))?;
+
+        let DeclLvl1Kind::Trait(tr) = &tree.body[0].value.kind else {
+            panic!("expected a trait");
+        };
+        assert_eq!(&*tr.name, "_!");
+        assert_eq!(tr.operator, Some(OperatorTrait::Cascade));
+        assert_eq!(tr.body.decls.len(), 2);
+
+        let tys = &tree.body[1].generics.as_ref().unwrap().tys;
+        let clauses = &tys[0].clauses;
+        assert_eq!(&*clauses[0].slice.value(), "operator +<T, T>");
+        assert_matches!(
+            &clauses[0].ty,
+            ClauseKind::RealType(Type {
+                kind: TypeKind::Operator {
+                    op: OperatorTrait::Add,
+                    generics,
+                },
+                ..
+            }) if generics.len() == 1
+        );
+        assert!(clauses[1].exclude);
+        assert_matches!(
+            &tys[1].clauses[0].ty,
+            ClauseKind::RealType(Type {
+                kind: TypeKind::Operator {
+                    op: OperatorTrait::Sub,
+                    generics,
+                },
+                ..
+            }) if generics.len() == 1
+        );
+
+        let DeclLvl1Kind::Impl(im) = &tree.body[2].value.kind else {
+            panic!("expected an impl");
+        };
+        assert_matches!(
+            im.tr.kind,
+            TypeKind::Operator {
+                op: OperatorTrait::BitNot,
+                ..
+            }
+        );
+
+        let error = parse_root(&mut Tokenizer::new("trait operator + {}".into())).unwrap_err();
+        assert_matches!(
+            error,
+            ParserError::UnexpectedToken(box UnexpectedToken { expected, .. }) if expected == [Expected::OperatorTrait]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn empty_trait() -> Result<(), ParserError> {
+        let tree = parse_root(&mut Tokenizer::new("trait A {} struct B {}".into()))?;
+        assert_eq!(tree.body.len(), 2);
+
+        Ok(())
+    }
+}
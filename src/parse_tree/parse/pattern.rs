@@ -12,7 +12,10 @@ use crate::{
     },
 };
 
-use super::{error::ParserError, expr::parse_generics_instance};
+use super::{
+    error::{Expected, ParserError},
+    expr::parse_generics_instance,
+};
 
 type PatternResult = Result<Pattern, ParserError>;
 
@@ -117,7 +120,11 @@ fn parse_value(tokenizer: &mut Tokenizer) -> PatternResult {
             tokenizer.next()?;
             let next = tokenizer.next()?;
             let TokenKind::Identifier(name) = next.kind else {
-                return Err(ParserError::unexpected_token(next));
+                return Err(ParserError::expected(
+                    next,
+                    &[Expected::Identifier],
+                    "after `mut`",
+                ));
             };
 
             return Ok(Pattern {
@@ -132,7 +139,11 @@ fn parse_value(tokenizer: &mut Tokenizer) -> PatternResult {
                 peek.kind
             {
                 let Some(name) = IdentPath::try_parse(tokenizer)? else {
-                    return Err(ParserError::unexpected_token(peek));
+                    return Err(ParserError::expected(
+                        peek,
+                        &[Expected::Identifier],
+                        "in a pattern",
+                    ));
                 };
 
                 let generics = parse_generics_instance(tokenizer)?;
@@ -158,14 +169,18 @@ fn parse_value(tokenizer: &mut Tokenizer) -> PatternResult {
                 },
             });
         }
-        _ => return Err(ParserError::unexpected_token(peek)),
+        _ => return Err(ParserError::expected(peek, &[Expected::Pattern], "")),
     }
 }
 
 fn parse_initializer_pattern(tokenizer: &mut Tokenizer) -> Result<InitializerPattern, ParserError> {
     let next = tokenizer.next()?;
     let TokenKind::Symbol(Symbol::BraceOpen) = next.kind else {
-        return Err(ParserError::unexpected_token(next));
+        return Err(ParserError::expected(
+            next,
+            &[Expected::Symbol(Symbol::BraceOpen)],
+            "to open the initializer",
+        ));
     };
     let start = next.slice;
 
@@ -198,13 +213,26 @@ fn parse_initializer_pattern(tokenizer: &mut Tokenizer) -> Result<InitializerPat
                     TokenKind::Symbol(Symbol::BraceClose) => {
                         break;
                     }
-                    _ => return Err(ParserError::unexpected_token(peek)),
+                    _ => {
+                        return Err(ParserError::expected(
+                            peek,
+                            &[
+                                Expected::Symbol(Symbol::Comma),
+                                Expected::Symbol(Symbol::BraceClose),
+                            ],
+                            "after a field pattern",
+                        ))
+                    }
                 }
             }
 
             let next = tokenizer.next()?;
             let TokenKind::Symbol(Symbol::BraceClose) = next.kind else {
-                return Err(ParserError::unexpected_token(next));
+                return Err(ParserError::expected(
+                    next,
+                    &[Expected::Symbol(Symbol::BraceClose)],
+                    "to close the initializer",
+                ));
             };
 
             return Ok(InitializerPattern {
@@ -231,13 +259,26 @@ fn parse_initializer_pattern(tokenizer: &mut Tokenizer) -> Result<InitializerPat
                     TokenKind::Symbol(Symbol::BraceClose) => {
                         break;
                     }
-                    _ => return Err(ParserError::unexpected_token(peek)),
+                    _ => {
+                        return Err(ParserError::expected(
+                            peek,
+                            &[
+                                Expected::Symbol(Symbol::Comma),
+                                Expected::Symbol(Symbol::BraceClose),
+                            ],
+                            "after a pattern",
+                        ))
+                    }
                 }
             }
 
             let next = tokenizer.next()?;
             let TokenKind::Symbol(Symbol::BraceClose) = next.kind else {
-                return Err(ParserError::unexpected_token(next));
+                return Err(ParserError::expected(
+                    next,
+                    &[Expected::Symbol(Symbol::BraceClose)],
+                    "to close the initializer",
+                ));
             };
 
             return Ok(InitializerPattern {
@@ -253,18 +294,30 @@ fn parse_named_initializer_pattern(
 ) -> Result<NamedInitializerPattern, ParserError> {
     let next = tokenizer.next()?;
     let TokenKind::Symbol(Symbol::Dot) = next.kind else {
-        return Err(ParserError::unexpected_token(next));
+        return Err(ParserError::expected(
+            next,
+            &[Expected::Symbol(Symbol::Dot)],
+            "in a named initializer",
+        ));
     };
     let start = next.slice;
 
     let next = tokenizer.next()?;
     let TokenKind::Identifier(name) = next.kind else {
-        return Err(ParserError::unexpected_token(next));
+        return Err(ParserError::expected(
+            next,
+            &[Expected::Identifier],
+            "after `.`",
+        ));
     };
 
     let next = tokenizer.next()?;
     let TokenKind::Symbol(Symbol::Assign) = next.kind else {
-        return Err(ParserError::unexpected_token(next));
+        return Err(ParserError::expected(
+            next,
+            &[Expected::Symbol(Symbol::Assign)],
+            "after a field name",
+        ));
     };
 
     let value = parse_pattern(tokenizer)?;
@@ -5,18 +5,25 @@ use crate::{
             pattern::parse_pattern,
         },
         statement::{
-            Block, IfClause, IfClauseKind, IfCondition, IfStatement, LetMatchClause,
-            LetMatchElseStatement, MatchBlock, MatchBlockKind, MatchClause, MatchStatement,
-            ReturnStatement, Statement, StatementKind,
+            Block, BreakStatement, ForInStatement, ForStatement, GotoStatement, IfClause,
+            IfClauseKind, IfCondition, IfStatement, LabelStatement, LetMatchClause,
+            LetMatchElseStatement, LoopStatement, MatchBlock, MatchBlockKind, MatchClause,
+            MatchStatement, ReturnStatement, Statement, StatementKind, VariableName,
+            WhileStatement,
         },
     },
+    string::StringSlice,
     tokenizer::{
         token::{Keyword, Symbol, TokenKind},
-        Tokenizer,
+        TokenizeError, Tokenizer,
     },
 };
 
-use super::{decl::parse_var_decl, error::ParserError};
+use super::{
+    decl::{parse_var_decl, parse_var_decl_unterminated},
+    error::{Expected, ParserError},
+    recover,
+};
 
 type StatementResult = Result<Option<Statement>, ParserError>;
 
@@ -42,6 +49,45 @@ pub fn parse_statement(tokenizer: &mut Tokenizer) -> StatementResult {
         }));
     }
 
+    if let Some(for_statement) = parse_for(tokenizer)? {
+        return Ok(Some(for_statement));
+    }
+
+    if let Some(while_statement) = parse_while(tokenizer)? {
+        return Ok(Some(Statement {
+            slice: while_statement.slice.clone(),
+            kind: StatementKind::While(while_statement),
+        }));
+    }
+
+    if let Some(loop_statement) = parse_loop(tokenizer)? {
+        return Ok(Some(Statement {
+            slice: loop_statement.slice.clone(),
+            kind: StatementKind::Loop(loop_statement),
+        }));
+    }
+
+    if let Some(label) = parse_label(tokenizer)? {
+        return Ok(Some(Statement {
+            slice: label.slice.clone(),
+            kind: StatementKind::Label(label),
+        }));
+    }
+
+    if let Some(goto) = parse_goto(tokenizer)? {
+        return Ok(Some(Statement {
+            slice: goto.slice.clone(),
+            kind: StatementKind::Goto(goto),
+        }));
+    }
+
+    if let Some(brk) = parse_break(tokenizer)? {
+        return Ok(Some(Statement {
+            slice: brk.slice.clone(),
+            kind: StatementKind::Break(brk),
+        }));
+    }
+
     if let Some(decl) = parse_var_decl(tokenizer)? {
         return Ok(Some(Statement {
             slice: decl.slice.clone(),
@@ -54,7 +100,11 @@ pub fn parse_statement(tokenizer: &mut Tokenizer) -> StatementResult {
         let end = peek.slice.clone();
 
         let TokenKind::Symbol(Symbol::Semicolon) = peek.kind else {
-            return Err(ParserError::unexpected_token(peek));
+            return Err(ParserError::expected(
+                peek,
+                &[Expected::Symbol(Symbol::Semicolon)],
+                "after an expression",
+            ));
         };
         tokenizer.next()?;
 
@@ -98,23 +148,51 @@ pub fn parse_block(tokenizer: &mut Tokenizer) -> Result<Option<Block>, ParserErr
 
     loop {
         let peek = tokenizer.peek(0)?;
-        let Some(statement) = parse_statement(tokenizer)? else {
-            return Err(ParserError::unexpected_token(peek));
-        };
 
-        statements.push(statement);
-
-        let next = tokenizer.peek(0)?;
+        match peek.kind {
+            TokenKind::Symbol(Symbol::BraceClose) => {
+                tokenizer.next()?;
+
+                return Ok(Some(Block {
+                    slice: start.merge(&peek.slice),
+                    statements,
+                }));
+            }
+            TokenKind::Eof => {
+                return Err(ParserError::expected(
+                    peek,
+                    &[Expected::Symbol(Symbol::BraceClose)],
+                    "to close the block",
+                ))
+            }
+            _ => {}
+        }
 
-        if let TokenKind::Symbol(Symbol::BraceClose) = next.kind {
-            tokenizer.next()?;
-            let end = next.slice;
+        let error = match parse_statement(tokenizer) {
+            Ok(Some(statement)) => {
+                statements.push(statement);
+                continue;
+            }
+            Ok(None) => ParserError::expected(
+                peek.clone(),
+                &[Expected::Statement, Expected::Symbol(Symbol::BraceClose)],
+                "in a block",
+            ),
+            // Nothing left to recover
+            Err(error @ ParserError::TokenizeError(TokenizeError::UnexpectedEof(_))) => {
+                return Err(error);
+            }
+            Err(error) => error,
+        };
 
-            return Ok(Some(Block {
-                slice: start.merge(&end),
-                statements,
-            }));
-        }
+        let slice = match recover(tokenizer, error, false) {
+            Some(skipped) => peek.slice.merge(&skipped),
+            None => peek.slice,
+        };
+        statements.push(Statement {
+            slice,
+            kind: StatementKind::Error,
+        });
     }
 }
 
@@ -134,17 +212,29 @@ fn parse_return(tokenizer: &mut Tokenizer) -> Result<Option<ReturnStatement>, Pa
 
         let next = tokenizer.next()?;
         let TokenKind::Symbol(Symbol::ParenOpen) = next.kind else {
-            return Err(ParserError::unexpected_token(next));
+            return Err(ParserError::expected(
+                next,
+                &[Expected::Symbol(Symbol::ParenOpen)],
+                "after `if`",
+            ));
         };
 
         let peek = tokenizer.peek(0)?;
         let Some(expr) = parse_expr(tokenizer)? else {
-            return Err(ParserError::unexpected_token(peek));
+            return Err(ParserError::expected(
+                peek,
+                &[Expected::Expr],
+                "in a condition",
+            ));
         };
 
         let next = tokenizer.next()?;
         let TokenKind::Symbol(Symbol::ParenClose) = next.kind else {
-            return Err(ParserError::unexpected_token(next));
+            return Err(ParserError::expected(
+                next,
+                &[Expected::Symbol(Symbol::ParenClose)],
+                "after the condition",
+            ));
         };
 
         Some(expr)
@@ -154,7 +244,14 @@ fn parse_return(tokenizer: &mut Tokenizer) -> Result<Option<ReturnStatement>, Pa
 
     let next = tokenizer.next()?;
     let TokenKind::Symbol(Symbol::Semicolon) = next.kind else {
-        return Err(ParserError::unexpected_token(next));
+        return Err(ParserError::expected(
+            next,
+            &[
+                Expected::Symbol(Symbol::Semicolon),
+                Expected::Keyword(Keyword::If),
+            ],
+            "after a return",
+        ));
     };
 
     return Ok(Some(ReturnStatement {
@@ -164,6 +261,337 @@ fn parse_return(tokenizer: &mut Tokenizer) -> Result<Option<ReturnStatement>, Pa
     }));
 }
 
+/// Parses both `for (init, condition, step)` and `for (x in range)`
+fn parse_for(tokenizer: &mut Tokenizer) -> StatementResult {
+    let peek = tokenizer.peek(0)?;
+    let TokenKind::Keyword(Keyword::For) = peek.kind else {
+        return Ok(None);
+    };
+    tokenizer.next()?;
+    let start = peek.slice;
+
+    let next = tokenizer.next()?;
+    let TokenKind::Symbol(Symbol::ParenOpen) = next.kind else {
+        return Err(ParserError::expected(
+            next,
+            &[Expected::Symbol(Symbol::ParenOpen)],
+            "after `for`",
+        ));
+    };
+
+    if let Some(for_in) = parse_for_in(tokenizer, &start)? {
+        return Ok(Some(Statement {
+            slice: for_in.slice.clone(),
+            kind: StatementKind::ForIn(for_in),
+        }));
+    }
+
+    let init = parse_var_decl_unterminated(tokenizer)?;
+
+    let next = tokenizer.next()?;
+    let TokenKind::Symbol(Symbol::Comma) = next.kind else {
+        let expected: &[Expected] = match init {
+            Some(_) => &[Expected::Symbol(Symbol::Comma)],
+            None => &[Expected::Decl, Expected::Symbol(Symbol::Comma)],
+        };
+        return Err(ParserError::expected(
+            next,
+            expected,
+            "after the loop initializer",
+        ));
+    };
+
+    let condition = parse_expr(tokenizer)?;
+
+    let next = tokenizer.next()?;
+    let TokenKind::Symbol(Symbol::Comma) = next.kind else {
+        let expected: &[Expected] = match condition {
+            Some(_) => &[Expected::Symbol(Symbol::Comma)],
+            None => &[Expected::Expr, Expected::Symbol(Symbol::Comma)],
+        };
+        return Err(ParserError::expected(
+            next,
+            expected,
+            "after the loop condition",
+        ));
+    };
+
+    let step = parse_expr(tokenizer)?;
+
+    let next = tokenizer.next()?;
+    let TokenKind::Symbol(Symbol::ParenClose) = next.kind else {
+        let expected: &[Expected] = match step {
+            Some(_) => &[Expected::Symbol(Symbol::ParenClose)],
+            None => &[Expected::Expr, Expected::Symbol(Symbol::ParenClose)],
+        };
+        return Err(ParserError::expected(next, expected, "after the loop step"));
+    };
+
+    let block = parse_loop_block(tokenizer)?;
+
+    return Ok(Some(Statement {
+        slice: start.merge(&block.slice),
+        kind: StatementKind::For(ForStatement {
+            slice: start.merge(&block.slice),
+            init,
+            condition,
+            step,
+            block,
+        }),
+    }));
+}
+
+/// The rest of a `for` after its `(`, if it's followed by `x in` or `mut x in`
+fn parse_for_in(
+    tokenizer: &mut Tokenizer,
+    start: &StringSlice,
+) -> Result<Option<ForInStatement>, ParserError> {
+    let is_mut = tokenizer.peek(0)?.kind == TokenKind::Keyword(Keyword::Mut);
+    let offset = is_mut as usize;
+
+    let name = match tokenizer.peek(offset)?.kind {
+        TokenKind::Identifier(name) => VariableName::Identifier(name),
+        TokenKind::Keyword(Keyword::Discard) => VariableName::Discard,
+        _ => return Ok(None),
+    };
+
+    let TokenKind::Keyword(Keyword::In) = tokenizer.peek(offset + 1)?.kind else {
+        return Ok(None);
+    };
+
+    for _ in 0..offset + 2 {
+        tokenizer.next()?;
+    }
+
+    let peek = tokenizer.peek(0)?;
+    let Some(value) = parse_expr(tokenizer)? else {
+        return Err(ParserError::expected(peek, &[Expected::Expr], "after `in`"));
+    };
+
+    let next = tokenizer.next()?;
+    let TokenKind::Symbol(Symbol::ParenClose) = next.kind else {
+        return Err(ParserError::expected(
+            next,
+            &[Expected::Symbol(Symbol::ParenClose)],
+            "after the iterated value",
+        ));
+    };
+
+    let block = parse_loop_block(tokenizer)?;
+
+    return Ok(Some(ForInStatement {
+        slice: start.merge(&block.slice),
+        is_mut,
+        name,
+        value,
+        block,
+    }));
+}
+
+fn parse_while(tokenizer: &mut Tokenizer) -> Result<Option<WhileStatement>, ParserError> {
+    let peek = tokenizer.peek(0)?;
+    let TokenKind::Keyword(Keyword::While) = peek.kind else {
+        return Ok(None);
+    };
+    tokenizer.next()?;
+    let start = peek.slice;
+
+    let next = tokenizer.next()?;
+    let TokenKind::Symbol(Symbol::ParenOpen) = next.kind else {
+        return Err(ParserError::expected(
+            next,
+            &[Expected::Symbol(Symbol::ParenOpen)],
+            "after `while`",
+        ));
+    };
+
+    let peek = tokenizer.peek(0)?;
+    let Some(condition) = parse_expr(tokenizer)? else {
+        return Err(ParserError::expected(
+            peek,
+            &[Expected::Expr],
+            "in a condition",
+        ));
+    };
+
+    let next = tokenizer.next()?;
+    let TokenKind::Symbol(Symbol::ParenClose) = next.kind else {
+        return Err(ParserError::expected(
+            next,
+            &[Expected::Symbol(Symbol::ParenClose)],
+            "after the condition",
+        ));
+    };
+
+    let block = parse_loop_block(tokenizer)?;
+
+    return Ok(Some(WhileStatement {
+        slice: start.merge(&block.slice),
+        condition,
+        block,
+    }));
+}
+
+fn parse_loop(tokenizer: &mut Tokenizer) -> Result<Option<LoopStatement>, ParserError> {
+    let peek = tokenizer.peek(0)?;
+    let TokenKind::Keyword(Keyword::Loop) = peek.kind else {
+        return Ok(None);
+    };
+    tokenizer.next()?;
+    let start = peek.slice;
+
+    let block = parse_loop_block(tokenizer)?;
+
+    return Ok(Some(LoopStatement {
+        slice: start.merge(&block.slice),
+        block,
+    }));
+}
+
+fn parse_loop_block(tokenizer: &mut Tokenizer) -> Result<Block, ParserError> {
+    let peek = tokenizer.peek(0)?;
+    let Some(block) = parse_block(tokenizer)? else {
+        return Err(ParserError::expected(
+            peek,
+            &[Expected::Symbol(Symbol::BraceOpen)],
+            "for the loop body",
+        ));
+    };
+
+    return Ok(block);
+}
+
+fn parse_label(tokenizer: &mut Tokenizer) -> Result<Option<LabelStatement>, ParserError> {
+    let peek = tokenizer.peek(0)?;
+    let TokenKind::Keyword(Keyword::Label) = peek.kind else {
+        return Ok(None);
+    };
+    tokenizer.next()?;
+    let start = peek.slice;
+
+    let next = tokenizer.next()?;
+    let TokenKind::Identifier(name) = next.kind else {
+        return Err(ParserError::expected(
+            next,
+            &[Expected::Identifier],
+            "after `label`",
+        ));
+    };
+
+    if let Some(block) = parse_block(tokenizer)? {
+        return Ok(Some(LabelStatement {
+            slice: start.merge(&block.slice),
+            name,
+            block: Some(block),
+        }));
+    }
+
+    let next = tokenizer.next()?;
+    let TokenKind::Symbol(Symbol::Semicolon) = next.kind else {
+        return Err(ParserError::expected(
+            next,
+            &[
+                Expected::Symbol(Symbol::Semicolon),
+                Expected::Symbol(Symbol::BraceOpen),
+            ],
+            "after a label name",
+        ));
+    };
+
+    return Ok(Some(LabelStatement {
+        slice: start.merge(&next.slice),
+        name,
+        block: None,
+    }));
+}
+
+fn parse_goto(tokenizer: &mut Tokenizer) -> Result<Option<GotoStatement>, ParserError> {
+    let peek = tokenizer.peek(0)?;
+    let TokenKind::Keyword(Keyword::Goto) = peek.kind else {
+        return Ok(None);
+    };
+    tokenizer.next()?;
+    let start = peek.slice;
+
+    let next = tokenizer.next()?;
+    let TokenKind::Identifier(label) = next.kind else {
+        return Err(ParserError::expected(
+            next,
+            &[Expected::Identifier],
+            "after `goto`",
+        ));
+    };
+
+    let next = tokenizer.next()?;
+    let TokenKind::Symbol(Symbol::Semicolon) = next.kind else {
+        return Err(ParserError::expected(
+            next,
+            &[Expected::Symbol(Symbol::Semicolon)],
+            "after a goto",
+        ));
+    };
+
+    return Ok(Some(GotoStatement {
+        slice: start.merge(&next.slice),
+        label,
+    }));
+}
+
+fn parse_break(tokenizer: &mut Tokenizer) -> Result<Option<BreakStatement>, ParserError> {
+    let peek = tokenizer.peek(0)?;
+    let TokenKind::Keyword(Keyword::Break) = peek.kind else {
+        return Ok(None);
+    };
+    tokenizer.next()?;
+    let start = peek.slice;
+
+    let peek = tokenizer.peek(0)?;
+    let label = match peek.kind {
+        TokenKind::Identifier(label) => {
+            tokenizer.next()?;
+            Some(label)
+        }
+        _ => None,
+    };
+
+    let peek = tokenizer.peek(0)?;
+    let condition = if let TokenKind::Keyword(Keyword::If) = peek.kind {
+        tokenizer.next()?;
+
+        let peek = tokenizer.peek(0)?;
+        let Some(expr) = parse_expr(tokenizer)? else {
+            return Err(ParserError::expected(peek, &[Expected::Expr], "after `if`"));
+        };
+
+        Some(expr)
+    } else {
+        None
+    };
+
+    let next = tokenizer.next()?;
+    let TokenKind::Symbol(Symbol::Semicolon) = next.kind else {
+        let expected: &[Expected] = match (&label, &condition) {
+            (_, Some(_)) => &[Expected::Symbol(Symbol::Semicolon)],
+            (Some(_), None) => &[
+                Expected::Symbol(Symbol::Semicolon),
+                Expected::Keyword(Keyword::If),
+            ],
+            (None, None) => &[
+                Expected::Symbol(Symbol::Semicolon),
+                Expected::Keyword(Keyword::If),
+                Expected::Identifier,
+            ],
+        };
+        return Err(ParserError::expected(next, expected, "after a break"));
+    };
+
+    return Ok(Some(BreakStatement {
+        slice: start.merge(&next.slice),
+        label,
+        condition,
+    }));
+}
+
 fn parse_match(tokenizer: &mut Tokenizer) -> Result<Option<MatchStatement>, ParserError> {
     let peek = tokenizer.peek(0)?;
     let TokenKind::Keyword(Keyword::Match) = peek.kind else {
@@ -174,22 +602,38 @@ fn parse_match(tokenizer: &mut Tokenizer) -> Result<Option<MatchStatement>, Pars
 
     let next = tokenizer.next()?;
     let TokenKind::Symbol(Symbol::ParenOpen) = next.kind else {
-        return Err(ParserError::unexpected_token(next));
+        return Err(ParserError::expected(
+            next,
+            &[Expected::Symbol(Symbol::ParenOpen)],
+            "after `match`",
+        ));
     };
 
     let peek = tokenizer.peek(0)?;
     let Some(value) = parse_expr(tokenizer)? else {
-        return Err(ParserError::unexpected_token(peek));
+        return Err(ParserError::expected(
+            peek,
+            &[Expected::Expr],
+            "after `match (`",
+        ));
     };
 
     let next = tokenizer.next()?;
     let TokenKind::Symbol(Symbol::ParenClose) = next.kind else {
-        return Err(ParserError::unexpected_token(next));
+        return Err(ParserError::expected(
+            next,
+            &[Expected::Symbol(Symbol::ParenClose)],
+            "after the matched value",
+        ));
     };
 
     let next = tokenizer.next()?;
     let TokenKind::Symbol(Symbol::BraceOpen) = next.kind else {
-        return Err(ParserError::unexpected_token(next));
+        return Err(ParserError::expected(
+            next,
+            &[Expected::Symbol(Symbol::BraceOpen)],
+            "to open the match",
+        ));
     };
 
     let peek = tokenizer.peek(0)?;
@@ -225,7 +669,11 @@ fn parse_match_clause(tokenizer: &mut Tokenizer) -> Result<MatchClause, ParserEr
 
     let next = tokenizer.next()?;
     let TokenKind::Symbol(Symbol::WideArrow) = next.kind else {
-        return Err(ParserError::unexpected_token(next));
+        return Err(ParserError::expected(
+            next,
+            &[Expected::Symbol(Symbol::WideArrow)],
+            "after a pattern",
+        ));
     };
 
     let block = parse_match_block(tokenizer)?;
@@ -240,7 +688,11 @@ fn parse_match_block(tokenizer: &mut Tokenizer) -> Result<MatchBlock, ParserErro
     let peek = tokenizer.peek(0)?;
     if let TokenKind::Symbol(Symbol::BraceOpen) = peek.kind {
         let Some(block) = parse_block(tokenizer)? else {
-            return Err(ParserError::unexpected_token(peek));
+            return Err(ParserError::expected(
+                peek,
+                &[Expected::Symbol(Symbol::BraceOpen)],
+                "to open the block",
+            ));
         };
 
         return Ok(MatchBlock {
@@ -250,7 +702,11 @@ fn parse_match_block(tokenizer: &mut Tokenizer) -> Result<MatchBlock, ParserErro
     }
 
     let Some(statement) = parse_statement(tokenizer)? else {
-        return Err(ParserError::unexpected_token(peek))?;
+        return Err(ParserError::expected(
+            peek,
+            &[Expected::Statement, Expected::Symbol(Symbol::BraceOpen)],
+            "after `=>`",
+        ))?;
     };
 
     return Ok(MatchBlock {
@@ -268,12 +724,20 @@ fn parse_let_match_else(
 
     let next = tokenizer.next()?;
     let TokenKind::Keyword(Keyword::Else) = next.kind else {
-        return Err(ParserError::unexpected_token(next));
+        return Err(ParserError::expected(
+            next,
+            &[Expected::Keyword(Keyword::Else)],
+            "after a `let match`",
+        ));
     };
 
     let peek = tokenizer.peek(0)?;
     let Some(block) = parse_block(tokenizer)? else {
-        return Err(ParserError::unexpected_token(peek));
+        return Err(ParserError::expected(
+            peek,
+            &[Expected::Symbol(Symbol::BraceOpen)],
+            "after `else`",
+        ));
     };
 
     return Ok(Some(LetMatchElseStatement {
@@ -295,7 +759,11 @@ fn parse_if_statement(tokenizer: &mut Tokenizer) -> Result<Option<IfStatement>,
 
     let peek = tokenizer.peek(0)?;
     let Some(block) = parse_block(tokenizer)? else {
-        return Err(ParserError::unexpected_token(peek));
+        return Err(ParserError::expected(
+            peek,
+            &[Expected::Symbol(Symbol::BraceOpen)],
+            "after the condition",
+        ));
     };
 
     let mut last = block.slice.clone();
@@ -333,7 +801,11 @@ fn parse_else_block(tokenizer: &mut Tokenizer) -> Result<Option<IfCondition>, Pa
 
         let peek = tokenizer.peek(0)?;
         let Some(block) = parse_block(tokenizer)? else {
-            return Err(ParserError::unexpected_token(peek));
+            return Err(ParserError::expected(
+                peek,
+                &[Expected::Symbol(Symbol::BraceOpen)],
+                "after the condition",
+            ));
         };
 
         return Ok(Some(IfCondition {
@@ -345,7 +817,14 @@ fn parse_else_block(tokenizer: &mut Tokenizer) -> Result<Option<IfCondition>, Pa
 
     let peek = tokenizer.peek(0)?;
     let Some(block) = parse_block(tokenizer)? else {
-        return Err(ParserError::unexpected_token(peek));
+        return Err(ParserError::expected(
+            peek,
+            &[
+                Expected::Symbol(Symbol::BraceOpen),
+                Expected::Keyword(Keyword::If),
+            ],
+            "after `else`",
+        ));
     };
 
     return Ok(Some(IfCondition {
@@ -365,18 +844,30 @@ fn parse_if_condition_kind(tokenizer: &mut Tokenizer) -> Result<IfClause, Parser
 
     let next = tokenizer.next()?;
     let TokenKind::Symbol(Symbol::ParenOpen) = next.kind else {
-        return Err(ParserError::unexpected_token(next));
+        return Err(ParserError::expected(
+            next,
+            &[Expected::Symbol(Symbol::ParenOpen)],
+            "after `if`",
+        ));
     };
     let start = next.slice;
 
     let peek = tokenizer.peek(0)?;
     let Some(expr) = parse_expr(tokenizer)? else {
-        return Err(ParserError::unexpected_token(peek));
+        return Err(ParserError::expected(
+            peek,
+            &[Expected::Expr],
+            "in a condition",
+        ));
     };
 
     let next = tokenizer.next()?;
     let TokenKind::Symbol(Symbol::ParenClose) = next.kind else {
-        return Err(ParserError::unexpected_token(next));
+        return Err(ParserError::expected(
+            next,
+            &[Expected::Symbol(Symbol::ParenClose)],
+            "after the condition",
+        ));
     };
 
     return Ok(IfClause {
@@ -400,24 +891,36 @@ fn parse_let_match_clause(
 
     let next = tokenizer.next()?;
     let TokenKind::Symbol(Symbol::ParenOpen) = next.kind else {
-        return Err(ParserError::unexpected_token(next));
+        return Err(ParserError::expected(
+            next,
+            &[Expected::Symbol(Symbol::ParenOpen)],
+            "after `let match`",
+        ));
     };
 
     let pat = parse_pattern(tokenizer)?;
 
     let next = tokenizer.next()?;
     let TokenKind::Symbol(Symbol::WideArrow) = next.kind else {
-        return Err(ParserError::unexpected_token(next));
+        return Err(ParserError::expected(
+            next,
+            &[Expected::Symbol(Symbol::WideArrow)],
+            "after the pattern",
+        ));
     };
 
     let peek = tokenizer.peek(0)?;
     let Some(value) = parse_expr(tokenizer)? else {
-        return Err(ParserError::unexpected_token(peek));
+        return Err(ParserError::expected(peek, &[Expected::Expr], "after `=>`"));
     };
 
     let next = tokenizer.next()?;
     let TokenKind::Symbol(Symbol::ParenClose) = next.kind else {
-        return Err(ParserError::unexpected_token(next));
+        return Err(ParserError::expected(
+            next,
+            &[Expected::Symbol(Symbol::ParenClose)],
+            "after the matched value",
+        ));
     };
     let end = next.slice;
 
@@ -427,3 +930,217 @@ fn parse_let_match_clause(
         value,
     }));
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        parse_tree::{
+            decl::{DeclLvl1Kind, DeclLvl2Kind, FuncBody, FuncBodyKind},
+            expr::{BinOp, Expr, ExprKind},
+            parse::{error::ParserError, parse_root},
+            statement::{BreakStatement, Statement, StatementKind, VariableName},
+        },
+        tokenizer::Tokenizer,
+    };
+
+    use super::parse_statement;
+
+    fn statement(src: &str) -> Result<Statement, ParserError> {
+        let mut tokenizer = Tokenizer::new(src.into());
+        return Ok(parse_statement(&mut tokenizer)?.expect("expected a statement"));
+    }
+
+    #[test]
+    fn loops() -> Result<(), ParserError> {
+        let StatementKind::For(stmt) = statement("for (let x = 0, x < 5, x++) {}")?.kind else {
+            panic!("expected a for loop");
+        };
+        assert_eq!(&*stmt.init.unwrap().slice.value(), "let x = 0");
+        assert!(stmt.condition.is_some() && stmt.step.is_some());
+
+        let StatementKind::For(stmt) = statement("for (,,) {}")?.kind else {
+            panic!("expected a for loop");
+        };
+        assert!(stmt.init.is_none() && stmt.condition.is_none() && stmt.step.is_none());
+
+        let StatementKind::ForIn(stmt) = statement("for (mut x in 0..5) { Print(x); }")?.kind
+        else {
+            panic!("expected a for in loop");
+        };
+        assert!(stmt.is_mut);
+        assert_eq!(stmt.name, VariableName::Identifier("x".into()));
+        assert!(matches!(
+            stmt.value.kind,
+            ExprKind::BinOp {
+                op: BinOp::Range,
+                ..
+            }
+        ));
+        assert_eq!(stmt.block.statements.len(), 1);
+
+        let StatementKind::While(stmt) = statement("while (x < 5) {}")?.kind else {
+            panic!("expected a while loop");
+        };
+        assert_eq!(&*stmt.condition.slice.value(), "x < 5");
+
+        let StatementKind::Loop(stmt) = statement("loop { Tick(); }")?.kind else {
+            panic!("expected a loop");
+        };
+        assert_eq!(&*stmt.slice.value(), "loop { Tick(); }");
+
+        assert!(statement("for (x in 0..5 {}").is_err());
+        assert!(statement("while x {}").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn labels() -> Result<(), ParserError> {
+        let StatementKind::Label(label) = statement("label x;")?.kind else {
+            panic!("expected a label");
+        };
+        assert_eq!(&*label.name, "x");
+        assert!(label.block.is_none());
+
+        let StatementKind::Goto(goto) = statement("goto x;")?.kind else {
+            panic!("expected a goto");
+        };
+        assert_eq!(&*goto.label, "x");
+
+        let StatementKind::Label(label) =
+            statement("label a { label b { break; } break a; break b if done; }")?.kind
+        else {
+            panic!("expected a label");
+        };
+        let statements: Vec<_> = label.block.unwrap().statements;
+        let [Statement {
+            kind: StatementKind::Label(inner),
+            ..
+        }, Statement {
+            kind: StatementKind::Break(a),
+            ..
+        }, Statement {
+            kind: StatementKind::Break(b),
+            ..
+        }] = &statements[..]
+        else {
+            panic!("expected a nested label and two breaks, got {statements:#?}");
+        };
+        assert!(matches!(
+            inner.block.as_ref().unwrap().statements[..],
+            [Statement {
+                kind: StatementKind::Break(BreakStatement {
+                    label: None,
+                    condition: None,
+                    ..
+                }),
+                ..
+            }]
+        ));
+        assert_eq!(a.label.as_deref(), Some("a"));
+        assert!(a.condition.is_none());
+        assert_eq!(b.label.as_deref(), Some("b"));
+        assert_eq!(&*b.condition.as_ref().unwrap().slice.value(), "done");
+
+        assert!(statement("label;").is_err());
+        assert!(statement("goto x").is_err());
+        assert!(statement("break a b;").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn bubble_sort() -> Result<(), ParserError> {
+        // The sort from spec/Examples.md, with the `:` the parser wants before generic arguments
+        let src = "where
+                T : Compare:<T>;
+            func BubbleSort(arr: ref mut [T]) {
+                for (i in 0..(arr.Size - 1)) {
+                    mut swapped = false;
+                    for (j in 0..(arr.Size - i - 1)) {
+                        if (arr[j] > arr[j+1]) {
+                            let tmp = arr[j];
+                            arr[j] = arr[j+1];
+                            arr[j+1] = tmp;
+                            swapped = true;
+                        }
+                    }
+                    break if (!swapped);
+                }
+            }";
+        let tree = parse_root(&mut Tokenizer::new(src.into()))?;
+
+        let DeclLvl1Kind::Lvl2(decl) = &tree.body[0].value.kind else {
+            panic!("expected a function");
+        };
+        let DeclLvl2Kind::Function(func) = &decl.kind else {
+            panic!("expected a function");
+        };
+        let Some(FuncBody {
+            kind: FuncBodyKind::Block(block),
+            ..
+        }) = &func.body
+        else {
+            panic!("expected a block body");
+        };
+
+        let [Statement {
+            kind: StatementKind::ForIn(outer),
+            ..
+        }] = &block.statements[..]
+        else {
+            panic!("expected a single for in loop");
+        };
+        assert_eq!(&*outer.value.slice.value(), "0..(arr.Size - 1)");
+
+        let [_, Statement {
+            kind: StatementKind::ForIn(inner),
+            ..
+        }, Statement {
+            kind: StatementKind::Break(brk),
+            ..
+        }] = &outer.block.statements[..]
+        else {
+            panic!("expected a nested for in loop");
+        };
+        assert_eq!(inner.name, VariableName::Identifier("j".into()));
+        assert_eq!(brk.label, None);
+        assert_eq!(
+            &*brk.condition.as_ref().unwrap().slice.value(),
+            "(!swapped)"
+        );
+        let [Statement {
+            kind: StatementKind::If(swap),
+            ..
+        }] = &inner.block.statements[..]
+        else {
+            panic!("expected a single if");
+        };
+        let swap: Vec<_> = swap.conditions[0]
+            .block
+            .statements
+            .iter()
+            .map(|it| &it.kind)
+            .collect();
+        assert!(matches!(
+            swap[..],
+            [
+                StatementKind::Decl(_),
+                StatementKind::Expr(Expr {
+                    kind: ExprKind::Assign { .. },
+                    ..
+                }),
+                StatementKind::Expr(Expr {
+                    kind: ExprKind::Assign { .. },
+                    ..
+                }),
+                StatementKind::Expr(Expr {
+                    kind: ExprKind::Assign { .. },
+                    ..
+                })
+            ]
+        ));
+
+        Ok(())
+    }
+}
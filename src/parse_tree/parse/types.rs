@@ -3,13 +3,17 @@ use crate::{
         types::{RefKind, Type, TypeKind},
         IdentPath,
     },
+    string::StringSlice,
     tokenizer::{
-        token::{Keyword, Number, Symbol, TokenKind},
+        token::{Keyword, Number, NumberValue, OperatorTrait, Symbol, TokenKind},
         Tokenizer,
     },
 };
 
-use super::{decl::parse_struct_body, error::ParserError};
+use super::{
+    decl::parse_struct_body,
+    error::{Expected, ParserError},
+};
 
 pub fn parse_type(tokenizer: &mut Tokenizer) -> Result<Type, ParserError> {
     let peek = tokenizer.peek(0)?;
@@ -23,59 +27,63 @@ pub fn parse_type(tokenizer: &mut Tokenizer) -> Result<Type, ParserError> {
     }
 
     if let Some(idents) = IdentPath::try_parse(tokenizer)? {
-        let start = idents.slice.clone();
-        let peek = tokenizer.peek(0)?;
-        if let TokenKind::Symbol(Symbol::Colon) = peek.kind {
-            tokenizer.next()?;
-
-            let next = tokenizer.next()?;
-            let TokenKind::Symbol(Symbol::Less) = next.kind else {
-                return Err(ParserError::unexpected_token(next));
-            };
-
-            let peek = tokenizer.peek(0)?;
+        let (generics, end) = match parse_generic_args(tokenizer)? {
+            Some((generics, end)) => (generics, end),
+            None => (vec![], idents.slice.clone()),
+        };
 
-            let TokenKind::Symbol(Symbol::Greater) = peek.kind else {
-                let mut params = vec![];
+        return Ok(Type {
+            slice: idents.slice.merge(&end),
+            kind: TypeKind::UserDefined {
+                path: idents,
+                generics,
+            },
+        });
+    }
 
-                loop {
-                    let ty = parse_type(tokenizer)?;
-                    params.push(ty);
+    if let TokenKind::Keyword(Keyword::Operator) = peek.kind {
+        tokenizer.next()?;
 
-                    let next = tokenizer.next()?;
-                    match next.kind {
-                        TokenKind::Symbol(Symbol::Comma) => {}
-                        TokenKind::Symbol(Symbol::Greater) => {
-                            return Ok(Type {
-                                slice: start.merge(&next.slice),
-                                kind: TypeKind::UserDefined {
-                                    path: idents,
-                                    generics: params,
-                                },
-                            });
-                        }
-                        _ => return Err(ParserError::unexpected_token(next)),
-                    }
-                }
-            };
-            tokenizer.next()?;
+        // A bare symbol like the `+` in `operator +<T, T>` is the binary operator if there is one
+        let next = tokenizer.next()?;
+        let bare = matches!(next.kind, TokenKind::Symbol(_));
+        let op = match next.kind {
+            TokenKind::OperatorTrait(op) => Some(op),
+            TokenKind::Symbol(symbol) => OperatorTrait::binary(symbol)
+                .or(OperatorTrait::prefix(symbol))
+                .or(OperatorTrait::postfix(symbol)),
+            _ => None,
+        };
+        let Some(op) = op else {
+            return Err(ParserError::expected(
+                next,
+                &[Expected::OperatorTrait],
+                "after `operator`",
+            ));
+        };
 
-            let end = peek.slice;
+        // The `:` isn't needed to tell the generics apart from a comparison here
+        let generics = match tokenizer.peek(0)?.kind {
+            TokenKind::Symbol(Symbol::Less) => {
+                tokenizer.next()?;
+                Some(parse_generic_list(tokenizer)?)
+            }
+            _ => parse_generic_args(tokenizer)?,
+        };
+        let (mut generics, end) = match generics {
+            Some((generics, end)) => (generics, end),
+            None => (vec![], next.slice),
+        };
 
-            return Ok(Type {
-                slice: start.merge(&end),
-                kind: TypeKind::UserDefined {
-                    path: idents,
-                    generics: vec![],
-                },
-            });
+        // The bare form lists every operand, the first one is the type that's being bound so
+        // `operator +<T, T>` means the same as `operator _+_<T>`
+        if bare && !generics.is_empty() {
+            generics.remove(0);
         }
+
         return Ok(Type {
-            slice: start,
-            kind: TypeKind::UserDefined {
-                path: idents,
-                generics: vec![],
-            },
+            slice: peek.slice.merge(&end),
+            kind: TypeKind::Operator { op, generics },
         });
     }
 
@@ -121,16 +129,25 @@ pub fn parse_type(tokenizer: &mut Tokenizer) -> Result<Type, ParserError> {
                     tokenizer.next()?;
                     let next = tokenizer.next()?;
                     let TokenKind::Number(Number {
-                        whole: count,
-                        decimal: 0.0,
+                        text: _,
+                        value: NumberValue::Int(count),
+                        suffix: None | Some(Keyword::Usize),
                     }) = next.kind
                     else {
-                        return Err(ParserError::unexpected_token(next));
+                        return Err(ParserError::expected(
+                            next,
+                            &[Expected::Integer],
+                            "for the array length",
+                        ));
                     };
 
                     let next = tokenizer.next()?;
                     let TokenKind::Symbol(Symbol::BracketClose) = next.kind else {
-                        return Err(ParserError::unexpected_token(next));
+                        return Err(ParserError::expected(
+                            next,
+                            &[Expected::Symbol(Symbol::BracketClose)],
+                            "after the array length",
+                        ));
                     };
 
                     return Ok(Type {
@@ -148,7 +165,16 @@ pub fn parse_type(tokenizer: &mut Tokenizer) -> Result<Type, ParserError> {
                         kind: TypeKind::Slice(Box::new(value)),
                     });
                 }
-                _ => return Err(ParserError::unexpected_token(peek)),
+                _ => {
+                    return Err(ParserError::expected(
+                        peek,
+                        &[
+                            Expected::Symbol(Symbol::Comma),
+                            Expected::Symbol(Symbol::BracketClose),
+                        ],
+                        "after the element type",
+                    ))
+                }
             }
         }
         TokenKind::Keyword(Keyword::Func) => {
@@ -157,7 +183,11 @@ pub fn parse_type(tokenizer: &mut Tokenizer) -> Result<Type, ParserError> {
 
             let peek = tokenizer.next()?;
             let TokenKind::Symbol(Symbol::ParenOpen) = peek.kind else {
-                return Err(ParserError::unexpected_token(peek));
+                return Err(ParserError::expected(
+                    peek,
+                    &[Expected::Symbol(Symbol::ParenOpen)],
+                    "after `func`",
+                ));
             };
             let mut peek = tokenizer.peek(0)?;
             let mut params = vec![];
@@ -168,7 +198,14 @@ pub fn parse_type(tokenizer: &mut Tokenizer) -> Result<Type, ParserError> {
                 peek = tokenizer.next()?;
 
                 let TokenKind::Symbol(Symbol::Comma | Symbol::ParenClose) = peek.kind else {
-                    return Err(ParserError::unexpected_token(peek));
+                    return Err(ParserError::expected(
+                        peek,
+                        &[
+                            Expected::Symbol(Symbol::Comma),
+                            Expected::Symbol(Symbol::ParenClose),
+                        ],
+                        "after a parameter type",
+                    ));
                 };
             }
 
@@ -205,6 +242,59 @@ pub fn parse_type(tokenizer: &mut Tokenizer) -> Result<Type, ParserError> {
                 kind: TypeKind::Struct(body),
             });
         }
-        _ => return Err(ParserError::unexpected_token(peek)),
+        _ => return Err(ParserError::expected(peek, &[Expected::Type], "")),
+    }
+}
+
+/// The `:<...>` after a type name, along with the closing `>`
+fn parse_generic_args(
+    tokenizer: &mut Tokenizer,
+) -> Result<Option<(Vec<Type>, StringSlice)>, ParserError> {
+    let TokenKind::Symbol(Symbol::Colon) = tokenizer.peek(0)?.kind else {
+        return Ok(None);
+    };
+    tokenizer.next()?;
+
+    let next = tokenizer.next()?;
+    let TokenKind::Symbol(Symbol::Less) = next.kind else {
+        return Err(ParserError::expected(
+            next,
+            &[Expected::Symbol(Symbol::Less)],
+            "for generic arguments",
+        ));
+    };
+
+    return Ok(Some(parse_generic_list(tokenizer)?));
+}
+
+/// The arguments after the opening `<`, up to and including the `>`
+fn parse_generic_list(tokenizer: &mut Tokenizer) -> Result<(Vec<Type>, StringSlice), ParserError> {
+    let peek = tokenizer.peek(0)?;
+    if let TokenKind::Symbol(Symbol::Greater) = peek.kind {
+        tokenizer.next()?;
+        return Ok((vec![], peek.slice));
+    }
+
+    let mut params = vec![];
+
+    loop {
+        let ty = parse_type(tokenizer)?;
+        params.push(ty);
+
+        let next = tokenizer.next()?;
+        match next.kind {
+            TokenKind::Symbol(Symbol::Comma) => {}
+            TokenKind::Symbol(Symbol::Greater) => return Ok((params, next.slice)),
+            _ => {
+                return Err(ParserError::expected(
+                    next,
+                    &[
+                        Expected::Symbol(Symbol::Comma),
+                        Expected::Symbol(Symbol::Greater),
+                    ],
+                    "in generic arguments",
+                ))
+            }
+        }
     }
 }
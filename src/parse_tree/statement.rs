@@ -24,6 +24,15 @@ pub enum StatementKind {
     LetMatchElse(LetMatchElseStatement),
     Match(MatchStatement),
     Return(ReturnStatement),
+    For(ForStatement),
+    ForIn(ForInStatement),
+    While(WhileStatement),
+    Loop(LoopStatement),
+    Label(LabelStatement),
+    Goto(GotoStatement),
+    Break(BreakStatement),
+    /// A statement that failed to parse
+    Error,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -33,6 +42,61 @@ pub struct ReturnStatement {
     pub condition: Option<Expr>,
 }
 
+/// C-style `for (init, condition, step)`, every part can be left out
+#[derive(Debug, Clone, PartialEq)]
+pub struct ForStatement {
+    pub slice: StringSlice,
+    pub init: Option<VariableDecl>,
+    pub condition: Option<Expr>,
+    pub step: Option<Expr>,
+    pub block: Block,
+}
+
+/// `for (x in range)`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ForInStatement {
+    pub slice: StringSlice,
+    pub is_mut: bool,
+    pub name: VariableName,
+    pub value: Expr,
+    pub block: Block,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct WhileStatement {
+    pub slice: StringSlice,
+    pub condition: Expr,
+    pub block: Block,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LoopStatement {
+    pub slice: StringSlice,
+    pub block: Block,
+}
+
+/// `label x;` marks a spot to `goto`, `label x { ... }` names a block to `break` out of
+#[derive(Debug, Clone, PartialEq)]
+pub struct LabelStatement {
+    pub slice: StringSlice,
+    pub name: Arc<str>,
+    pub block: Option<Block>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GotoStatement {
+    pub slice: StringSlice,
+    pub label: Arc<str>,
+}
+
+/// Without a label it breaks out of the innermost loop or labelled block
+#[derive(Debug, Clone, PartialEq)]
+pub struct BreakStatement {
+    pub slice: StringSlice,
+    pub label: Option<Arc<str>>,
+    pub condition: Option<Expr>,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct LetMatchElseStatement {
     pub slice: StringSlice,
@@ -1,7 +1,7 @@
 use crate::{
     string::StringSlice,
     tokenizer::{
-        token::{Keyword, Symbol, TokenKind},
+        token::{Keyword, OperatorTrait, Symbol, TokenKind},
         Tokenizer,
     },
 };
@@ -65,6 +65,12 @@ pub enum TypeKind {
         path: IdentPath,
         generics: Vec<Type>,
     },
+
+    /// `operator _+_:<T>`
+    Operator {
+        op: OperatorTrait,
+        generics: Vec<Type>,
+    },
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -86,7 +92,7 @@ impl TypeKind {
             TokenKind::Keyword(Keyword::I16) => Self::I16,
             TokenKind::Keyword(Keyword::U32) => Self::U32,
             TokenKind::Keyword(Keyword::I32) => Self::I32,
-            TokenKind::Keyword(Keyword::U64) => Self::U16,
+            TokenKind::Keyword(Keyword::U64) => Self::U64,
             TokenKind::Keyword(Keyword::I64) => Self::I64,
             TokenKind::Keyword(Keyword::Usize) => Self::Usize,
             TokenKind::Keyword(Keyword::Isize) => Self::Isize,
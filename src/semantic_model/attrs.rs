@@ -0,0 +1,667 @@
+use std::sync::Arc;
+
+use crate::{
+    parse_tree::{
+        decl::{
+            Abi, Attr, AttrParamKind, Attrs, DeclLvl1, DeclLvl1Kind, DeclLvl2, DeclLvl2Kind,
+            DeclModifier, EnumDecl, EnumDeclKind, FunctionDecl, GenericsDecl, ImplDecl, Linkage,
+            Sentinel, StructBody, StructDeclKind, TraitBody,
+        },
+        expr::{BinOp, Expr, ExprKind, PrimitiveExpr, PrimitiveExprKind},
+        types::{Type, TypeKind},
+        IdentPath, ParseTree,
+    },
+    string::StringSlice,
+    tokenizer::token::{Number, NumberValue, OperatorTrait},
+};
+
+use super::error::SemanticError;
+
+/// Gets the attribute and what it's written on, reports its own errors
+type Handler = fn(&mut Expansion, &Attr, Item);
+
+const BUILTIN_ATTRS: &[(&str, Handler)] = &[
+    ("Derive", derive),
+    ("Flags", flags),
+    ("OkWhen", ok_when),
+    ("NoneWhen", none_when),
+    ("Extern", link_extern),
+];
+
+/// Runs the built-in handler of every attribute in a file. Handlers can add declarations, like
+/// the impls of `Derive`, so this has to run before the symbols of the file are collected
+pub fn expand_attrs(tree: &mut ParseTree, errors: &mut Vec<SemanticError>) {
+    let mut expansion = Expansion {
+        errors,
+        decls: vec![],
+    };
+
+    for decl in &mut tree.body {
+        expansion.decl(decl);
+    }
+
+    tree.body.append(&mut expansion.decls);
+}
+
+struct Expansion<'e> {
+    errors: &'e mut Vec<SemanticError>,
+    /// Declarations made by handlers, added to the file once every attribute has run
+    decls: Vec<DeclModifier<DeclLvl1>>,
+}
+
+/// What an attribute is written on
+enum Item<'a> {
+    Decl {
+        decl: &'a mut DeclLvl1,
+        generics: Option<&'a GenericsDecl>,
+    },
+    /// A declaration in the body of a trait or impl
+    Member(&'a mut DeclLvl2),
+    Field,
+    Variant,
+}
+
+impl<'a> Item<'a> {
+    fn function(self) -> Option<&'a mut FunctionDecl> {
+        let decl = match self {
+            Item::Decl { decl, generics: _ } => match &mut decl.kind {
+                DeclLvl1Kind::Lvl2(decl) => decl,
+                _ => return None,
+            },
+            Item::Member(decl) => decl,
+            Item::Field | Item::Variant => return None,
+        };
+
+        return match &mut decl.kind {
+            DeclLvl2Kind::Function(func) => Some(func),
+            DeclLvl2Kind::Variable(_) => None,
+        };
+    }
+}
+
+impl Expansion<'_> {
+    fn decl(&mut self, decl: &mut DeclModifier<DeclLvl1>) {
+        for attr in self.attrs(&decl.attrs) {
+            let item = Item::Decl {
+                decl: &mut decl.value,
+                generics: decl.generics.as_ref(),
+            };
+            self.expand(&attr, item);
+        }
+
+        match &mut decl.value.kind {
+            DeclLvl1Kind::Struct(st) => {
+                if let StructDeclKind::Value(body) = &st.kind {
+                    self.fields(body);
+                }
+            }
+            DeclLvl1Kind::Union(un) => self.fields(&un.body),
+            DeclLvl1Kind::Enum(en) => match &en.kind {
+                EnumDeclKind::Int { ty: _, body } => {
+                    for param in &body.params {
+                        for attr in self.attrs(&param.attrs) {
+                            self.expand(&attr, Item::Variant);
+                        }
+                    }
+                }
+                EnumDeclKind::Value(body) => self.fields(body),
+            },
+            DeclLvl1Kind::Trait(tr) => self.members(&mut tr.body),
+            DeclLvl1Kind::Impl(im) => self.members(&mut im.body),
+            DeclLvl1Kind::Lvl2(_) | DeclLvl1Kind::Error => {}
+        }
+    }
+
+    fn fields(&mut self, body: &StructBody) {
+        for param in &body.params {
+            for attr in self.attrs(&param.attrs) {
+                self.expand(&attr, Item::Field);
+            }
+        }
+    }
+
+    fn members(&mut self, body: &mut TraitBody) {
+        for decl in &mut body.decls {
+            for attr in self.attrs(&decl.attrs) {
+                self.expand(&attr, Item::Member(&mut decl.value));
+            }
+        }
+    }
+
+    fn expand(&mut self, attr: &Attr, item: Item) {
+        let name = attr.name.path.join("::");
+
+        let Some((_, handler)) = BUILTIN_ATTRS.iter().find(|(it, _)| *it == name) else {
+            self.errors.push(SemanticError::UnknownAttribute {
+                path: attr.name.clone(),
+            });
+            return;
+        };
+
+        handler(self, attr, item);
+    }
+
+    /// The attributes on an item, leaving out and reporting the ones written more than once
+    fn attrs(&mut self, attrs: &Option<Attrs>) -> Vec<Attr> {
+        let mut unique: Vec<Attr> = vec![];
+        for attr in attrs.iter().flat_map(|it| &it.attrs) {
+            match unique.iter().find(|it| it.name.path == attr.name.path) {
+                Some(first) => self.errors.push(SemanticError::DuplicateAttribute {
+                    name: attr.name.path.join("::").into(),
+                    first: first.slice.clone(),
+                    second: attr.slice.clone(),
+                }),
+                None => unique.push(attr.clone()),
+            }
+        }
+
+        return unique;
+    }
+
+    fn misplaced(&mut self, attr: &Attr, allowed: &'static str) {
+        self.errors.push(SemanticError::MisplacedAttribute {
+            slice: attr.slice.clone(),
+            name: attr.name.path.join("::").into(),
+            allowed,
+        });
+    }
+
+    fn invalid_params(&mut self, attr: &Attr, slice: &StringSlice, expected: &'static str) {
+        self.errors.push(SemanticError::InvalidAttributeParams {
+            slice: slice.clone(),
+            name: attr.name.path.join("::").into(),
+            expected,
+        });
+    }
+}
+
+fn named_type(slice: &StringSlice, name: &Arc<str>, generics: Vec<Type>) -> Type {
+    return Type {
+        slice: slice.clone(),
+        kind: TypeKind::UserDefined {
+            path: IdentPath {
+                slice: slice.clone(),
+                path: vec![name.clone()],
+            },
+            generics,
+        },
+    };
+}
+
+/// `impl tr for Name:<T, ...>` with an empty body. Everything points at `slice`, so errors in
+/// the impl point at the attribute that made it
+fn impl_decl(
+    slice: &StringSlice,
+    tr: Type,
+    name: &Arc<str>,
+    generics: Option<&GenericsDecl>,
+) -> DeclModifier<DeclLvl1> {
+    let args = match generics {
+        Some(generics) => generics
+            .tys
+            .iter()
+            .map(|it| named_type(slice, &it.name, vec![]))
+            .collect(),
+        None => vec![],
+    };
+
+    let im = ImplDecl {
+        slice: slice.clone(),
+        tr,
+        ty: named_type(slice, name, args),
+        body: TraitBody {
+            slice: slice.clone(),
+            decls: vec![],
+        },
+    };
+
+    return DeclModifier {
+        slice: slice.clone(),
+        attrs: None,
+        generics: generics.cloned(),
+        is_pub: false,
+        value: DeclLvl1 {
+            slice: slice.clone(),
+            kind: DeclLvl1Kind::Impl(im),
+        },
+    };
+}
+
+/// `#[Derive(Debug, Eq)]` implements each trait for the type
+fn derive(ex: &mut Expansion, attr: &Attr, item: Item) {
+    const ALLOWED: &str = "structs, enums and unions";
+    const EXPECTED: &str = "the traits to derive, like `Derive(Debug, Eq)`";
+
+    let Item::Decl { decl, generics } = item else {
+        ex.misplaced(attr, ALLOWED);
+        return;
+    };
+
+    let name = match &decl.kind {
+        DeclLvl1Kind::Struct(st) => &st.name,
+        DeclLvl1Kind::Enum(en) => &en.name,
+        DeclLvl1Kind::Union(un) => &un.name,
+        _ => {
+            ex.misplaced(attr, ALLOWED);
+            return;
+        }
+    };
+
+    if attr.params.is_empty() {
+        ex.invalid_params(attr, &attr.slice, EXPECTED);
+        return;
+    }
+
+    let mut derived: Vec<&IdentPath> = vec![];
+    for param in &attr.params {
+        let (None, AttrParamKind::Path(path)) = (&param.name, &param.value) else {
+            ex.invalid_params(attr, &param.slice, EXPECTED);
+            continue;
+        };
+
+        if let Some(first) = derived.iter().find(|it| it.path == path.path) {
+            ex.errors.push(SemanticError::DuplicateDerive {
+                name: path.path.join("::").into(),
+                first: first.slice.clone(),
+                second: path.slice.clone(),
+            });
+            continue;
+        }
+        derived.push(path);
+
+        let tr = Type {
+            slice: path.slice.clone(),
+            kind: TypeKind::UserDefined {
+                path: path.clone(),
+                generics: vec![],
+            },
+        };
+        ex.decls.push(impl_decl(&param.slice, tr, name, generics));
+    }
+}
+
+/// `#[Flags]` makes an int enum a set of bits, which can be combined with the bit operators
+fn flags(ex: &mut Expansion, attr: &Attr, item: Item) {
+    let Item::Decl { decl, generics } = item else {
+        ex.misplaced(attr, "int enums");
+        return;
+    };
+
+    let DeclLvl1Kind::Enum(EnumDecl {
+        name,
+        kind: EnumDeclKind::Int { ty: _, body },
+        ..
+    }) = &decl.kind
+    else {
+        ex.misplaced(attr, "int enums");
+        return;
+    };
+
+    if let Some(param) = attr.params.first() {
+        ex.invalid_params(attr, &param.slice, "no params");
+    }
+
+    // Variants without a value count up from the one before, like in any int enum
+    let mut next = Some(0);
+    for variant in &body.params {
+        let value = match &variant.value {
+            Some(value) => const_int(value),
+            None => next,
+        };
+        next = value.and_then(|it| it.checked_add(1));
+
+        match value {
+            Some(value) if value.is_power_of_two() => {}
+            Some(_) => ex.errors.push(SemanticError::InvalidFlag {
+                slice: variant.slice.clone(),
+            }),
+            // Only the variant with the value is reported, not the ones counting up from it
+            None if variant.value.is_some() => ex.errors.push(SemanticError::UncheckedFlag {
+                slice: variant.slice.clone(),
+            }),
+            None => {}
+        }
+    }
+
+    let this = named_type(&attr.slice, name, vec![]);
+    for op in [
+        OperatorTrait::BitOr,
+        OperatorTrait::BitAnd,
+        OperatorTrait::BitXor,
+        OperatorTrait::BitNot,
+    ] {
+        let args = match op {
+            OperatorTrait::BitNot => vec![],
+            _ => vec![this.clone()],
+        };
+
+        let tr = Type {
+            slice: attr.slice.clone(),
+            kind: TypeKind::Operator { op, generics: args },
+        };
+        ex.decls.push(impl_decl(&attr.slice, tr, name, generics));
+    }
+}
+
+/// Works out an int literal and the arithmetic and bit operators on them, `None` for anything
+/// else or if it overflows
+fn const_int(expr: &Expr) -> Option<u64> {
+    return match &expr.kind {
+        ExprKind::Primitive(PrimitiveExpr {
+            kind:
+                PrimitiveExprKind::Number(Number {
+                    value: NumberValue::Int(value),
+                    ..
+                }),
+            ..
+        }) => Some(*value),
+        ExprKind::BinOp { lhs, op, rhs } => {
+            let (lhs, rhs) = (const_int(lhs)?, const_int(rhs)?);
+            match op {
+                BinOp::Add => lhs.checked_add(rhs),
+                BinOp::Sub => lhs.checked_sub(rhs),
+                BinOp::Mul => lhs.checked_mul(rhs),
+                BinOp::Shl => {
+                    let rhs = rhs.try_into().ok()?;
+                    lhs.checked_shl(rhs).filter(|it| it >> rhs == lhs)
+                }
+                BinOp::Shr => lhs.checked_shr(rhs.try_into().ok()?),
+                BinOp::BitAnd => Some(lhs & rhs),
+                BinOp::BitOr => Some(lhs | rhs),
+                BinOp::BitXor => Some(lhs ^ rhs),
+                _ => None,
+            }
+        }
+        _ => None,
+    };
+}
+
+fn ok_when(ex: &mut Expansion, attr: &Attr, item: Item) {
+    sentinel(ex, attr, item, Sentinel::OkWhen);
+}
+
+fn none_when(ex: &mut Expansion, attr: &Attr, item: Item) {
+    sentinel(ex, attr, item, Sentinel::NoneWhen);
+}
+
+/// `#[OkWhen(true)]` and `#[NoneWhen(0)]` take the return value that has a special meaning
+fn sentinel(ex: &mut Expansion, attr: &Attr, item: Item, make: fn(PrimitiveExpr) -> Sentinel) {
+    let Some(func) = item.function().filter(|it| it.ret.is_some()) else {
+        ex.misplaced(attr, "functions that return a value");
+        return;
+    };
+
+    let [param] = &attr.params[..] else {
+        ex.invalid_params(attr, &attr.slice, "a single value, like `OkWhen(true)`");
+        return;
+    };
+
+    let (None, AttrParamKind::Value(value)) = (&param.name, &param.value) else {
+        ex.invalid_params(attr, &param.slice, "a single value, like `OkWhen(true)`");
+        return;
+    };
+
+    func.sentinel = Some(make(value.clone()));
+}
+
+/// `#[Extern(C, symbol = "name")]` links the function with another language's conventions
+fn link_extern(ex: &mut Expansion, attr: &Attr, item: Item) {
+    const EXPECTED: &str =
+        "an ABI and an optional symbol name, like `Extern(C, symbol = \"name\")`";
+
+    let Some(func) = item.function() else {
+        ex.misplaced(attr, "functions");
+        return;
+    };
+
+    let mut abi = None;
+    let mut symbol = None;
+
+    for param in &attr.params {
+        match (&param.name, &param.value) {
+            (None, AttrParamKind::Path(path))
+                if abi.is_none() && path.path.len() == 1 && &*path.path[0] == "C" =>
+            {
+                abi = Some(Abi::C);
+            }
+            (
+                Some(name),
+                AttrParamKind::Value(PrimitiveExpr {
+                    kind: PrimitiveExprKind::String(value),
+                    ..
+                }),
+            ) if &**name == "symbol" && symbol.is_none() => {
+                symbol = Some(value.clone());
+            }
+            _ => {
+                ex.invalid_params(attr, &param.slice, EXPECTED);
+                return;
+            }
+        }
+    }
+
+    let Some(abi) = abi else {
+        ex.invalid_params(attr, &attr.slice, EXPECTED);
+        return;
+    };
+
+    func.linkage = Some(Linkage {
+        abi,
+        symbol: symbol.unwrap_or_else(|| func.name.clone()),
+    });
+}
+
+#[cfg(test)]
+mod test {
+    use std::assert_matches::assert_matches;
+
+    use crate::{
+        parse_tree::{
+            decl::{Abi, DeclLvl1Kind, DeclLvl2Kind, Sentinel},
+            expr::PrimitiveExprKind,
+            parse::{error::ParserError, parse_root},
+            types::TypeKind,
+            ParseTree,
+        },
+        semantic_model::error::SemanticError,
+        tokenizer::{token::OperatorTrait, Tokenizer},
+    };
+
+    use super::expand_attrs;
+
+    fn expand(src: &str) -> Result<(ParseTree, Vec<SemanticError>), ParserError> {
+        let mut tree = parse_root(&mut Tokenizer::new(src.into()))?;
+
+        let mut errors = vec![];
+        expand_attrs(&mut tree, &mut errors);
+
+        return Ok((tree, errors));
+    }
+
+    fn impls(tree: &ParseTree) -> Vec<(String, String)> {
+        return tree
+            .body
+            .iter()
+            .filter_map(|it| match &it.value.kind {
+                DeclLvl1Kind::Impl(im) => Some((
+                    match &im.tr.kind {
+                        TypeKind::Operator { op, generics } => {
+                            format!("{}:{}", op.as_str(), generics.len())
+                        }
+                        _ => im.tr.slice.value().to_string(),
+                    },
+                    match &im.ty.kind {
+                        TypeKind::UserDefined { path, generics } => {
+                            format!("{}:{}", path.path.join("::"), generics.len())
+                        }
+                        _ => panic!("expected a named type"),
+                    },
+                )),
+                _ => None,
+            })
+            .collect();
+    }
+
+    #[test]
+    fn derive_and_flags() -> Result<(), ParserError> {
+        let (tree, errors) = expand(
+            "#[Derive(Debug, Std::Eq)] where T; struct Wrap { value: T }
+
+            #[Flags]
+            enum Access : u8 { Read = 1, Write = 0x2, Both = 3 }",
+        )?;
+
+        let [SemanticError::InvalidFlag { slice }] = &errors[..] else {
+            panic!("expected a single invalid flag, got {errors:?}");
+        };
+        assert_eq!(&*slice.value(), "Both = 3");
+
+        assert_eq!(
+            impls(&tree),
+            [
+                ("Debug", "Wrap:1"),
+                ("Std::Eq", "Wrap:1"),
+                ("_|_:1", "Access:0"),
+                ("_&_:1", "Access:0"),
+                ("_^_:1", "Access:0"),
+                ("~_:0", "Access:0"),
+            ]
+            .map(|(tr, ty)| (tr.to_string(), ty.to_string()))
+        );
+
+        // The impls keep the generics of the type they're derived for
+        assert!(tree.body[2].generics.is_some());
+        assert_matches!(
+            &tree.body[4].value.kind,
+            DeclLvl1Kind::Impl(im)
+                if matches!(im.tr.kind, TypeKind::Operator { op: OperatorTrait::BitOr, .. })
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn flag_values() -> Result<(), ParserError> {
+        let (_, errors) = expand(
+            "#[Flags] enum Shifted : u32 { A = 1 << 2, B = 0x8, C = (1 << 4) | 0 }
+            #[Flags] enum Counted : u8 { None, One, Two, Three, Four = 4, Five }
+            #[Flags] enum Unknown : u8 { A = Other::A, B, C = 2 }",
+        )?;
+
+        let [SemanticError::InvalidFlag { slice: none }, SemanticError::InvalidFlag { slice: three }, SemanticError::InvalidFlag { slice: five }, SemanticError::UncheckedFlag { slice: unknown }] =
+            &errors[..]
+        else {
+            panic!("expected three invalid flags and an unchecked one, got {errors:?}");
+        };
+        assert_eq!(&*none.value(), "None");
+        assert_eq!(&*three.value(), "Three");
+        assert_eq!(&*five.value(), "Five");
+        assert_eq!(&*unknown.value(), "A = Other::A");
+
+        Ok(())
+    }
+
+    #[test]
+    fn duplicates() -> Result<(), ParserError> {
+        let (tree, errors) = expand(
+            "#[Flags] #[Flags] enum F : u8 { A = 1 }
+            #[Derive(Eq, Debug, Eq)] struct S { #[Inline] #[Inline] x: i32 }",
+        )?;
+
+        let [SemanticError::DuplicateAttribute {
+            name: flags,
+            first,
+            second,
+        }, SemanticError::DuplicateDerive {
+            name: eq,
+            first: _,
+            second: _,
+        }, SemanticError::DuplicateAttribute {
+            name: inline,
+            first: _,
+            second: _,
+        }, SemanticError::UnknownAttribute { path: _ }] = &errors[..]
+        else {
+            panic!("expected the repeats to be reported, got {errors:?}");
+        };
+        assert_eq!(&**flags, "Flags");
+        assert!(first.start < second.start);
+        assert_eq!(&**eq, "Eq");
+        assert_eq!(&**inline, "Inline");
+
+        // The repeats don't add impls of their own
+        assert_eq!(impls(&tree).len(), 6);
+
+        Ok(())
+    }
+
+    #[test]
+    fn functions() -> Result<(), ParserError> {
+        let (tree, errors) = expand(
+            r#"#[Extern(C)] func Puts(s: ref str): i32;
+            #[Extern(C, symbol = "glClear")] func Clear(mask: u32);
+            #[OkWhen(true)] func Init(): bool => true;"#,
+        )?;
+
+        assert_eq!(errors, vec![]);
+
+        let functions: Vec<_> = tree
+            .body
+            .iter()
+            .map(|it| match &it.value.kind {
+                DeclLvl1Kind::Lvl2(decl) => match &decl.kind {
+                    DeclLvl2Kind::Function(func) => func,
+                    _ => panic!("expected a function"),
+                },
+                _ => panic!("expected a function"),
+            })
+            .collect();
+
+        let puts = functions[0].linkage.as_ref().unwrap();
+        assert_eq!(puts.abi, Abi::C);
+        assert_eq!(&*puts.symbol, "Puts");
+        assert_eq!(&*functions[1].linkage.as_ref().unwrap().symbol, "glClear");
+        assert_matches!(
+            &functions[2].sentinel,
+            Some(Sentinel::OkWhen(value)) if value.kind == PrimitiveExprKind::Bool(true)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn invalid() -> Result<(), ParserError> {
+        let (tree, errors) = expand(
+            "#[Inline] func A();
+            #[Derive(Debug)] func B();
+            #[NoneWhen(0)] func C();
+            #[Derive(Eq = 1)] struct D { #[Default(0)] x: i32 }",
+        )?;
+
+        assert_eq!(tree.body.len(), 4);
+
+        let [SemanticError::UnknownAttribute { path: inline }, SemanticError::MisplacedAttribute {
+            slice: _,
+            name: derive,
+            allowed: _,
+        }, SemanticError::MisplacedAttribute {
+            slice: _,
+            name: none_when,
+            allowed: "functions that return a value",
+        }, SemanticError::InvalidAttributeParams {
+            slice: params,
+            name: _,
+            expected: _,
+        }, SemanticError::UnknownAttribute { path: default }] = &errors[..]
+        else {
+            panic!("expected five attribute errors, got {errors:?}");
+        };
+        assert_eq!(inline.path, ["Inline".into()]);
+        assert_eq!(&**derive, "Derive");
+        assert_eq!(&**none_when, "NoneWhen");
+        assert_eq!(&*params.value(), "Eq = 1");
+        assert_eq!(default.path, ["Default".into()]);
+
+        Ok(())
+    }
+}
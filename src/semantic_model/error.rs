@@ -0,0 +1,172 @@
+use std::{path::PathBuf, sync::Arc};
+
+use crate::{
+    parse_tree::{
+        expr::{AccessKind, BinOp, UnaryOp},
+        parse::error::ParserError,
+        IdentPath,
+    },
+    string::StringSlice,
+};
+
+use super::{format::FormatSpec, types::ResolvedType};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SemanticError {
+    DuplicateSymbol {
+        name: Arc<str>,
+        first: StringSlice,
+        second: StringSlice,
+    },
+    MissingImport {
+        slice: StringSlice,
+        path: PathBuf,
+    },
+    UnsupportedImport {
+        slice: StringSlice,
+        path: PathBuf,
+    },
+    ImportCycle {
+        slice: StringSlice,
+        cycle: Vec<PathBuf>,
+    },
+    ParseError {
+        path: PathBuf,
+        error: ParserError,
+    },
+    UnknownPath {
+        path: IdentPath,
+    },
+    AmbiguousPath {
+        path: IdentPath,
+        candidates: Vec<Vec<Arc<str>>>,
+    },
+    NotAType {
+        path: IdentPath,
+    },
+    GenericArity {
+        slice: StringSlice,
+        expected: usize,
+        found: usize,
+    },
+    InfiniteSize {
+        slice: StringSlice,
+        cycle: Vec<Vec<Arc<str>>>,
+    },
+    TypeMismatch {
+        slice: StringSlice,
+        expected: ResolvedType,
+        found: ResolvedType,
+    },
+    InvalidOperands {
+        slice: StringSlice,
+        op: BinOp,
+        lhs: ResolvedType,
+        rhs: ResolvedType,
+    },
+    InvalidOperand {
+        slice: StringSlice,
+        op: UnaryOp,
+        ty: ResolvedType,
+    },
+    ArgumentCount {
+        slice: StringSlice,
+        expected: usize,
+        found: usize,
+    },
+    /// An array initializer with a different number of elements than its `[T, N]` type
+    ArrayLength {
+        slice: StringSlice,
+        expected: usize,
+        found: usize,
+    },
+    NotCallable {
+        slice: StringSlice,
+        ty: ResolvedType,
+    },
+    NotIndexable {
+        slice: StringSlice,
+        ty: ResolvedType,
+    },
+    /// Assigning to something that isn't a variable, field, index or dereference
+    InvalidAssignment {
+        slice: StringSlice,
+    },
+    ImmutableAssignment {
+        slice: StringSlice,
+    },
+    /// `for in` over something that isn't a range
+    NotIterable {
+        slice: StringSlice,
+        ty: ResolvedType,
+    },
+    UnknownField {
+        slice: StringSlice,
+        ty: ResolvedType,
+        field: Arc<str>,
+    },
+    InvalidAccess {
+        slice: StringSlice,
+        access: AccessKind,
+        ty: ResolvedType,
+    },
+    InvalidCast {
+        slice: StringSlice,
+        from: ResolvedType,
+        to: ResolvedType,
+    },
+    LiteralOutOfRange {
+        slice: StringSlice,
+        ty: ResolvedType,
+    },
+    InvalidFormat {
+        slice: StringSlice,
+    },
+    FormatNotLiteral {
+        slice: StringSlice,
+    },
+    FormatArgCount {
+        slice: StringSlice,
+        expected: usize,
+        found: usize,
+    },
+    NotFormattable {
+        slice: StringSlice,
+        spec: FormatSpec,
+        ty: ResolvedType,
+    },
+    UnknownAttribute {
+        path: IdentPath,
+    },
+    /// `allowed` describes what the attribute can be used on, like "functions"
+    MisplacedAttribute {
+        slice: StringSlice,
+        name: Arc<str>,
+        allowed: &'static str,
+    },
+    /// `expected` describes the params the attribute takes
+    InvalidAttributeParams {
+        slice: StringSlice,
+        name: Arc<str>,
+        expected: &'static str,
+    },
+    DuplicateAttribute {
+        name: Arc<str>,
+        first: StringSlice,
+        second: StringSlice,
+    },
+    /// A trait written twice in `#[Derive(...)]`
+    DuplicateDerive {
+        name: Arc<str>,
+        first: StringSlice,
+        second: StringSlice,
+    },
+    /// A `#[Flags]` variant that isn't a single bit
+    InvalidFlag {
+        slice: StringSlice,
+    },
+    /// A `#[Flags]` variant whose value isn't a constant `Flags` can work out
+    UncheckedFlag {
+        slice: StringSlice,
+    },
+}
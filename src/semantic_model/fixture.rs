@@ -0,0 +1,52 @@
+use std::path::PathBuf;
+
+use crate::{
+    parse_tree::parse::{error::ParserError, parse_root},
+    tokenizer::Tokenizer,
+};
+
+use super::{
+    attrs::expand_attrs,
+    error::SemanticError,
+    import::SourceFile,
+    namespace::{NamespaceTree, Scope},
+    resolve::resolve_paths,
+    symbol::SymbolTable,
+};
+
+/// Parses each source as its own file and runs every pass up to path resolution like `main` does,
+/// then runs `pass` on the result
+pub fn analyze(
+    srcs: &[&str],
+    pass: impl FnOnce(&[Scope], &[SymbolTable], &[SourceFile], &mut Vec<SemanticError>),
+) -> Result<Vec<SemanticError>, ParserError> {
+    let mut errors = vec![];
+
+    let mut files = vec![];
+    for src in srcs {
+        let mut tree = parse_root(&mut Tokenizer::new((*src).into()))?;
+        expand_attrs(&mut tree, &mut errors);
+
+        files.push(SourceFile {
+            path: PathBuf::new(),
+            tree,
+            imports: vec![],
+        });
+    }
+
+    let tables: Vec<_> = files
+        .iter()
+        .map(|it| SymbolTable::build(&it.tree, &mut errors))
+        .collect();
+    let tree = NamespaceTree::build(&tables, &mut errors);
+    let scopes: Vec<_> = files
+        .iter()
+        .enumerate()
+        .map(|(idx, it)| tree.scope(idx, &it.tree, &mut errors))
+        .collect();
+    resolve_paths(&scopes, &files, &mut errors);
+
+    pass(&scopes, &tables, &files, &mut errors);
+
+    return Ok(errors);
+}
@@ -0,0 +1,164 @@
+use std::sync::Arc;
+
+use crate::{
+    parse_tree::{
+        expr::{Expr, ExprKind},
+        IdentPath,
+    },
+    string::StringSlice,
+};
+
+use super::namespace::Scope;
+
+/// Functions that take a format string followed by the values for its placeholders,
+/// unless something with the same name is declared
+pub const FORMAT_FUNCS: &[&str] = &["Print", "PrintLn"];
+
+/// The trait declared types implement to be formatted with `{}`
+pub const DISPLAY_TRAIT: &[&str] = &["Std", "Display"];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Placeholder {
+    /// The placeholder in the literal, braces included
+    pub slice: StringSlice,
+    pub arg: FormatArg,
+    pub spec: FormatSpec,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum FormatArg {
+    /// `{}` takes the next argument
+    Next,
+    /// `{name}` captures a variable
+    Named(IdentPath),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FormatSpec {
+    Display,
+    /// `:x`
+    LowerHex,
+    /// `:X`
+    UpperHex,
+    /// `:b`
+    Binary,
+    /// `:o`
+    Octal,
+}
+
+impl FormatSpec {
+    fn parse(spec: &str) -> Option<Self> {
+        return match spec {
+            "" => Some(Self::Display),
+            "x" => Some(Self::LowerHex),
+            "X" => Some(Self::UpperHex),
+            "b" => Some(Self::Binary),
+            "o" => Some(Self::Octal),
+            _ => None,
+        };
+    }
+}
+
+/// The path of a builtin formatting function if that's what `value` calls
+pub fn format_func<'a>(value: &'a Expr, scope: &Scope) -> Option<&'a IdentPath> {
+    let ExprKind::Variable {
+        path,
+        generics: None,
+    } = &value.kind
+    else {
+        return None;
+    };
+
+    if path.path.len() != 1 || !FORMAT_FUNCS.contains(&&*path.path[0]) {
+        return None;
+    }
+
+    return match scope.resolve(path) {
+        Ok(_) => None,
+        Err(_) => Some(path),
+    };
+}
+
+/// Finds the placeholders in the value of a string literal, `{{` and `}}` are escaped braces.
+/// Placeholders point into `literal` when the value is written there as is, escapes and stripped
+/// indentation move things around so then they point at the whole literal.
+/// Errors with the slice of the malformed placeholder
+pub fn parse_format(value: &str, literal: &StringSlice) -> Result<Vec<Placeholder>, StringSlice> {
+    let text = literal.value();
+    let offset = text
+        .find('"')
+        .map(|it| it + 1)
+        .filter(|it| text[*it..].starts_with(value));
+
+    let slice = |start: usize, end: usize| match offset {
+        Some(offset) => StringSlice {
+            start: literal.start + offset + start,
+            end: literal.start + offset + end,
+            ..literal.clone()
+        },
+        None => literal.clone(),
+    };
+
+    let mut placeholders = vec![];
+    let mut chars = value.char_indices();
+
+    while let Some((idx, c)) = chars.next() {
+        match c {
+            '}' => {
+                let Some((_, '}')) = chars.next() else {
+                    return Err(slice(idx, idx + 1));
+                };
+            }
+            '{' => {
+                if value[idx + 1..].starts_with('{') {
+                    chars.next();
+                    continue;
+                }
+
+                let mut end = None;
+                let mut content = String::new();
+
+                for (idx, c) in chars.by_ref() {
+                    if c == '}' {
+                        end = Some(idx);
+                        break;
+                    }
+                    content.push(c);
+                }
+
+                let Some(end) = end else {
+                    return Err(slice(idx, value.len()));
+                };
+
+                let placeholder = slice(idx, end + 1);
+
+                let (name, spec) = content.split_once(':').unwrap_or((&content, ""));
+                let valid_name = name
+                    .chars()
+                    .enumerate()
+                    .all(|(i, c)| c == '_' || c.is_alphabetic() || (i != 0 && c.is_alphanumeric()));
+
+                let (true, Some(spec)) = (valid_name, FormatSpec::parse(spec)) else {
+                    return Err(placeholder);
+                };
+
+                let arg = match name {
+                    "" => FormatArg::Next,
+                    name => FormatArg::Named(IdentPath {
+                        slice: slice(idx + 1, idx + 1 + name.len()),
+                        path: vec![Arc::from(name)],
+                    }),
+                };
+
+                placeholders.push(Placeholder {
+                    slice: placeholder,
+                    arg,
+                    spec,
+                });
+            }
+            _ => {}
+        }
+    }
+
+    return Ok(placeholders);
+}
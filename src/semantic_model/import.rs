@@ -0,0 +1,257 @@
+use std::{
+    collections::HashMap,
+    env, fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    parse_tree::{decl::ImportDecl, parse::parse_file, ParseTree},
+    string::source::SourceMap,
+    tokenizer::Tokenizer,
+};
+
+use super::error::SemanticError;
+
+const SYSTEM_PATHS: &[&str] = &["/usr/local/include", "/usr/include"];
+
+/// Turns import paths into canonical file paths
+#[derive(Debug, Clone)]
+pub struct ImportResolver {
+    pub root: PathBuf,
+    pub system: Vec<PathBuf>,
+}
+
+/// Every file reachable from the entry file, each parsed exactly once
+#[derive(Debug, Clone)]
+pub struct ImportGraph {
+    pub files: Vec<SourceFile>,
+    /// The source of every file that was read, including ones that failed to parse
+    pub sources: SourceMap,
+    index: HashMap<PathBuf, usize>,
+}
+
+#[derive(Debug, Clone)]
+pub struct SourceFile {
+    pub path: PathBuf,
+    pub tree: ParseTree,
+    pub imports: Vec<usize>,
+}
+
+impl ImportResolver {
+    /// `root` is the project root, system paths are taken from `GEKKER_PATH` if it's set
+    pub fn new(root: PathBuf) -> Self {
+        let system = match env::var_os("GEKKER_PATH") {
+            Some(paths) => env::split_paths(&paths).collect(),
+            None => SYSTEM_PATHS.iter().map(PathBuf::from).collect(),
+        };
+
+        return Self { root, system };
+    }
+
+    /// Finds the file an import points to, errors with the path that was looked for first
+    pub fn resolve(&self, from: &Path, import: &ImportDecl) -> Result<PathBuf, PathBuf> {
+        let path = &*import.path;
+
+        let candidates = if let Some(rest) = path.strip_prefix("~/") {
+            vec![self.root.join(rest)]
+        } else if let Some(rest) = path.strip_prefix("$/") {
+            self.system.iter().map(|it| it.join(rest)).collect()
+        } else {
+            let rest = path.strip_prefix("./").unwrap_or(path);
+            let dir = from.parent().unwrap_or(Path::new(""));
+            vec![dir.join(rest)]
+        };
+
+        for candidate in &candidates {
+            if let Ok(canonical) = candidate.canonicalize() {
+                return Ok(canonical);
+            }
+        }
+
+        return Err(candidates.into_iter().next().unwrap_or(path.into()));
+    }
+}
+
+impl ImportGraph {
+    pub fn load(
+        resolver: &ImportResolver,
+        entry: &Path,
+        errors: &mut Vec<SemanticError>,
+    ) -> Result<Self, io::Error> {
+        let mut graph = Self {
+            files: vec![],
+            sources: SourceMap::new(),
+            index: HashMap::new(),
+        };
+
+        let entry = entry.canonicalize()?;
+        let src = fs::read_to_string(&entry)?;
+
+        graph.visit(resolver, entry, src, &mut vec![], errors);
+
+        return Ok(graph);
+    }
+
+    pub fn get(&self, path: &Path) -> Option<&SourceFile> {
+        return self.index.get(path).map(|idx| &self.files[*idx]);
+    }
+
+    fn visit(
+        &mut self,
+        resolver: &ImportResolver,
+        path: PathBuf,
+        src: String,
+        stack: &mut Vec<usize>,
+        errors: &mut Vec<SemanticError>,
+    ) -> usize {
+        let file = self.sources.add(path.clone(), src.into());
+        let src = self.sources.get(file).unwrap().src.clone();
+
+        // The parts of the file that did parse are still checked
+        let (tree, parse_errors) = parse_file(&mut Tokenizer::for_file(file, src));
+        for error in parse_errors {
+            errors.push(SemanticError::ParseError {
+                path: path.clone(),
+                error,
+            });
+        }
+
+        let id = self.files.len();
+        self.index.insert(path.clone(), id);
+        self.files.push(SourceFile {
+            path: path.clone(),
+            tree: tree.clone(),
+            imports: vec![],
+        });
+
+        stack.push(id);
+
+        for import in &tree.imports {
+            let resolved = match resolver.resolve(&path, import) {
+                Ok(resolved) => resolved,
+                Err(missing) => {
+                    errors.push(SemanticError::MissingImport {
+                        slice: import.slice.clone(),
+                        path: missing,
+                    });
+                    continue;
+                }
+            };
+
+            if let Some(&dep) = self.index.get(&resolved) {
+                if let Some(pos) = stack.iter().position(|it| *it == dep) {
+                    let mut cycle: Vec<_> = stack[pos..]
+                        .iter()
+                        .map(|it| self.files[*it].path.clone())
+                        .collect();
+                    cycle.push(resolved);
+
+                    errors.push(SemanticError::ImportCycle {
+                        slice: import.slice.clone(),
+                        cycle,
+                    });
+                    continue;
+                }
+
+                self.files[id].imports.push(dep);
+                continue;
+            }
+
+            if resolved.extension().is_none_or(|it| it != "gek") {
+                errors.push(SemanticError::UnsupportedImport {
+                    slice: import.slice.clone(),
+                    path: resolved,
+                });
+                continue;
+            }
+
+            let Ok(src) = fs::read_to_string(&resolved) else {
+                errors.push(SemanticError::MissingImport {
+                    slice: import.slice.clone(),
+                    path: resolved,
+                });
+                continue;
+            };
+
+            let dep = self.visit(resolver, resolved, src, stack, errors);
+            self.files[id].imports.push(dep);
+        }
+
+        stack.pop();
+
+        return id;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        env, fs,
+        path::{Path, PathBuf},
+    };
+
+    use crate::semantic_model::error::SemanticError;
+
+    use super::{ImportGraph, ImportResolver};
+
+    /// A directory under the system temp dir that's removed again when dropped
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new(name: &str) -> std::io::Result<Self> {
+            let dir = env::temp_dir().join(name);
+            fs::create_dir_all(&dir)?;
+            return Ok(Self(dir));
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    #[test]
+    fn test_files() -> std::io::Result<()> {
+        let resolver = ImportResolver::new(env::current_dir()?);
+        let mut errors = vec![];
+        let graph = ImportGraph::load(&resolver, Path::new("test/Main.gek"), &mut errors)?;
+
+        assert!(errors.is_empty());
+        assert_eq!(graph.files.len(), 2);
+        assert_eq!(graph.files[0].imports, vec![1]);
+        assert!(graph.get(&Path::new("test/Test.gek").canonicalize()?).is_some());
+
+        Ok(())
+    }
+
+    #[test]
+    fn diamond_and_cycle() -> std::io::Result<()> {
+        let temp = TempDir::new("gekker_import_diamond_and_cycle")?;
+        let dir = &temp.0;
+        fs::create_dir_all(dir.join("sub"))?;
+        fs::write(dir.join("A.gek"), "import \"B.gek\"; import \"~/sub/C.gek\";")?;
+        fs::write(dir.join("B.gek"), "import \"./D.gek\";")?;
+        fs::write(dir.join("sub/C.gek"), "import \"../D.gek\";")?;
+        fs::write(dir.join("D.gek"), "import \"./A.gek\";")?;
+
+        let resolver = ImportResolver::new(dir.clone());
+        let mut errors = vec![];
+        let graph = ImportGraph::load(&resolver, &dir.join("A.gek"), &mut errors)?;
+
+        assert_eq!(graph.files.len(), 4);
+
+        let [SemanticError::ImportCycle { slice, cycle }] = &errors[..] else {
+            panic!("expected a single import cycle, got {errors:?}");
+        };
+        assert_eq!(&*slice.value(), "import \"./A.gek\";");
+
+        let location = slice.location(&graph.sources).unwrap();
+        assert_eq!(location.path, dir.join("D.gek").canonicalize()?);
+        assert_eq!((location.start.line, location.start.col), (1, 1));
+        assert_eq!(cycle.len(), 4);
+        assert_eq!(cycle.first(), cycle.last());
+
+        Ok(())
+    }
+}
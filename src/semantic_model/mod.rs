@@ -0,0 +1,12 @@
+pub mod attrs;
+pub mod error;
+#[cfg(test)]
+mod fixture;
+pub mod format;
+pub mod import;
+pub mod namespace;
+pub mod resolve;
+pub mod structs;
+pub mod symbol;
+pub mod typecheck;
+pub mod types;
@@ -0,0 +1,240 @@
+use std::{collections::HashMap, sync::Arc};
+
+use crate::parse_tree::{IdentPath, ParseTree};
+
+use super::{
+    error::SemanticError,
+    symbol::{Symbol, SymbolKind, SymbolTable},
+};
+
+/// The namespaces of every file in the import graph, merged into one tree
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NamespaceTree {
+    pub root: Namespace,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Namespace {
+    pub children: HashMap<Arc<str>, Namespace>,
+    pub symbols: HashMap<Arc<str>, GlobalSymbol>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GlobalSymbol {
+    /// Index of the declaring file in the import graph
+    pub file: usize,
+    pub symbol: Symbol,
+}
+
+/// A path that was resolved to a declared symbol
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResolvedPath<'a> {
+    pub symbol: &'a GlobalSymbol,
+    /// An enum variant or associated item of `symbol`
+    pub member: Option<Arc<str>>,
+}
+
+/// The names visible from inside a single file
+#[derive(Debug, Clone)]
+pub struct Scope<'a> {
+    pub tree: &'a NamespaceTree,
+    pub file: usize,
+    pub namespace: Vec<Arc<str>>,
+    pub usings: Vec<(Arc<str>, Vec<Arc<str>>)>,
+}
+
+impl NamespaceTree {
+    /// `tables` is indexed the same way as the files of the import graph
+    pub fn build(tables: &[SymbolTable], errors: &mut Vec<SemanticError>) -> Self {
+        let mut tree = Self::default();
+
+        for (file, table) in tables.iter().enumerate() {
+            let mut namespace = &mut tree.root;
+            for name in &table.namespace {
+                namespace = namespace.children.entry(name.clone()).or_default();
+            }
+
+            for symbol in &table.symbols {
+                if let Some(existing) = namespace.symbols.get(&symbol.name) {
+                    errors.push(SemanticError::DuplicateSymbol {
+                        name: symbol.name.clone(),
+                        first: existing.symbol.slice.clone(),
+                        second: symbol.slice.clone(),
+                    });
+                    continue;
+                }
+
+                namespace.symbols.insert(
+                    symbol.name.clone(),
+                    GlobalSymbol {
+                        file,
+                        symbol: symbol.clone(),
+                    },
+                );
+            }
+        }
+
+        return tree;
+    }
+
+    pub fn get_namespace(&self, path: &[Arc<str>]) -> Option<&Namespace> {
+        let mut namespace = &self.root;
+        for name in path {
+            namespace = namespace.children.get(name)?;
+        }
+        return Some(namespace);
+    }
+
+    pub fn get(&self, path: &[Arc<str>]) -> Option<&GlobalSymbol> {
+        let (name, parent) = path.split_last()?;
+        return self.get_namespace(parent)?.symbols.get(name);
+    }
+
+    /// Looks up an absolute path, optionally as a trailing member on a type
+    fn lookup(&self, path: &[Arc<str>], member: bool) -> Option<ResolvedPath<'_>> {
+        if !member {
+            return Some(ResolvedPath {
+                symbol: self.get(path)?,
+                member: None,
+            });
+        }
+
+        let (member, parent) = path.split_last()?;
+        let symbol = self.get(parent)?;
+        let (SymbolKind::Struct(_)
+        | SymbolKind::Enum(_)
+        | SymbolKind::Union(_)
+        | SymbolKind::Trait(_)) = symbol.symbol.kind
+        else {
+            return None;
+        };
+
+        return Some(ResolvedPath {
+            symbol,
+            member: Some(member.clone()),
+        });
+    }
+
+    pub fn scope(
+        &self,
+        file: usize,
+        tree: &ParseTree,
+        errors: &mut Vec<SemanticError>,
+    ) -> Scope<'_> {
+        let namespace = match &tree.namespace {
+            Some(namespace) => namespace.path.path.clone(),
+            None => vec![],
+        };
+
+        let mut scope = Scope {
+            tree: self,
+            file,
+            namespace,
+            usings: vec![],
+        };
+
+        for using in &tree.usings {
+            let Some(target) = scope.resolve_using(&using.path.path) else {
+                errors.push(SemanticError::UnknownPath {
+                    path: using.path.clone(),
+                });
+                continue;
+            };
+
+            let name = match &using.alias {
+                Some(alias) => alias.clone(),
+                None => using.path.path.last().unwrap().clone(),
+            };
+
+            scope.usings.push((name, target));
+        }
+
+        return scope;
+    }
+}
+
+impl<'a> Scope<'a> {
+    /// Every namespace enclosing this file, innermost first
+    fn enclosing(&self) -> impl Iterator<Item = Vec<Arc<str>>> + '_ {
+        return (0..=self.namespace.len())
+            .rev()
+            .map(|len| self.namespace[..len].to_vec());
+    }
+
+    fn resolve_using(&self, path: &[Arc<str>]) -> Option<Vec<Arc<str>>> {
+        for mut candidate in self.enclosing() {
+            candidate.extend_from_slice(path);
+
+            if self.tree.get_namespace(&candidate).is_some() || self.tree.get(&candidate).is_some()
+            {
+                return Some(candidate);
+            }
+        }
+
+        return None;
+    }
+
+    /// The error is boxed so every lookup's `Result` stays small
+    pub fn resolve(&self, path: &IdentPath) -> Result<ResolvedPath<'a>, Box<SemanticError>> {
+        let mut found = self.resolve_candidates(&path.path, false);
+
+        // Members are only considered when the path doesn't name a symbol itself
+        if found.is_empty() {
+            found = self.resolve_candidates(&path.path, true);
+        }
+
+        match found.len() {
+            0 => {
+                return Err(Box::new(SemanticError::UnknownPath { path: path.clone() }));
+            }
+            1 => return Ok(found.pop().unwrap()),
+            _ => {
+                return Err(Box::new(SemanticError::AmbiguousPath {
+                    path: path.clone(),
+                    candidates: found
+                        .into_iter()
+                        .map(|it| {
+                            let mut path = it.symbol.symbol.path.clone();
+                            path.extend(it.member);
+                            path
+                        })
+                        .collect(),
+                }));
+            }
+        }
+    }
+
+    fn resolve_candidates(&self, path: &[Arc<str>], member: bool) -> Vec<ResolvedPath<'a>> {
+        let mut found: Vec<ResolvedPath<'a>> = vec![];
+
+        let (first, rest) = path.split_first().unwrap();
+
+        for (name, target) in &self.usings {
+            if name != first {
+                continue;
+            }
+
+            let mut candidate = target.clone();
+            candidate.extend_from_slice(rest);
+
+            if let Some(resolved) = self.tree.lookup(&candidate, member)
+                && !found.contains(&resolved)
+            {
+                found.push(resolved);
+            }
+        }
+
+        for mut candidate in self.enclosing() {
+            candidate.extend_from_slice(path);
+
+            if let Some(resolved) = self.tree.lookup(&candidate, member) {
+                if !found.contains(&resolved) {
+                    found.push(resolved);
+                }
+                break;
+            }
+        }
+
+        return found;
+    }
+}
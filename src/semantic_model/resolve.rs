@@ -0,0 +1,505 @@
+use std::sync::Arc;
+
+use crate::parse_tree::{
+    decl::{
+        ClauseKind, DeclLvl1, DeclLvl1Kind, DeclLvl2, DeclLvl2Kind, DeclModifier, EnumDeclKind,
+        FuncBody, FuncBodyKind, FunctionDecl, GenericsDecl, StructBody, StructDeclKind,
+        VariableDecl,
+    },
+    expr::{ArrayInitializer, Expr, ExprKind, GenericsInstance, InitializerKind, InitializerList},
+    pattern::{InitializerPatternKind, Pattern, PatternKind},
+    statement::{Block, IfClauseKind, MatchBlockKind, Statement, StatementKind, VariableName},
+    types::Type,
+    IdentPath,
+};
+
+use super::{error::SemanticError, format::format_func, import::SourceFile, namespace::Scope};
+
+/// Checks that every path in types and expressions names a declared symbol
+pub fn resolve_paths(scopes: &[Scope], files: &[SourceFile], errors: &mut Vec<SemanticError>) {
+    for (scope, source) in scopes.iter().zip(files) {
+        let mut resolver = PathResolver {
+            scope,
+            generics: vec![],
+            locals: vec![],
+            errors,
+        };
+
+        for decl in &source.tree.body {
+            resolver.lvl_1_decl(decl);
+        }
+    }
+}
+
+struct PathResolver<'a, 'e> {
+    scope: &'a Scope<'a>,
+    generics: Vec<Arc<str>>,
+    locals: Vec<Arc<str>>,
+    errors: &'e mut Vec<SemanticError>,
+}
+
+impl PathResolver<'_, '_> {
+    fn push_generics(&mut self, generics: &Option<GenericsDecl>) -> usize {
+        let len = self.generics.len();
+
+        let Some(generics) = generics else {
+            return len;
+        };
+
+        for ty in &generics.tys {
+            self.generics.push(ty.name.clone());
+        }
+
+        for ty in &generics.tys {
+            for clause in &ty.clauses {
+                if let ClauseKind::RealType(ty) = &clause.ty {
+                    self.ty(ty);
+                }
+            }
+        }
+
+        return len;
+    }
+
+    fn lvl_1_decl(&mut self, decl: &DeclModifier<DeclLvl1>) {
+        let generics = self.push_generics(&decl.generics);
+        let locals = self.locals.len();
+
+        match &decl.value.kind {
+            DeclLvl1Kind::Struct(st) => match &st.kind {
+                StructDeclKind::Wrapper(ty) => self.ty(ty),
+                StructDeclKind::Value(body) => self.struct_body(body),
+            },
+            DeclLvl1Kind::Enum(en) => match &en.kind {
+                EnumDeclKind::Int { ty: _, body } => {
+                    for param in &body.params {
+                        if let Some(value) = &param.value {
+                            self.expr(value);
+                        }
+                    }
+                }
+                EnumDeclKind::Value(body) => self.struct_body(body),
+            },
+            DeclLvl1Kind::Union(un) => self.struct_body(&un.body),
+            DeclLvl1Kind::Trait(tr) => {
+                for decl in &tr.body.decls {
+                    self.lvl_2_decl(decl);
+                }
+            }
+            DeclLvl1Kind::Impl(im) => {
+                self.ty(&im.tr);
+                self.ty(&im.ty);
+                for decl in &im.body.decls {
+                    self.lvl_2_decl(decl);
+                }
+            }
+            DeclLvl1Kind::Lvl2(lvl2) => self.lvl_2_decl_raw(lvl2),
+            DeclLvl1Kind::Error => {}
+        }
+
+        self.locals.truncate(locals);
+        self.generics.truncate(generics);
+    }
+
+    fn lvl_2_decl(&mut self, decl: &DeclModifier<DeclLvl2>) {
+        let generics = self.push_generics(&decl.generics);
+
+        self.lvl_2_decl_raw(&decl.value);
+
+        self.generics.truncate(generics);
+    }
+
+    fn lvl_2_decl_raw(&mut self, decl: &DeclLvl2) {
+        match &decl.kind {
+            DeclLvl2Kind::Function(func) => self.func(func),
+            DeclLvl2Kind::Variable(var) => self.var(var),
+        }
+    }
+
+    fn func(&mut self, func: &FunctionDecl) {
+        let locals = self.locals.len();
+
+        for param in &func.params {
+            self.ty(&param.ty);
+            self.locals.push(param.name.clone());
+        }
+
+        if let Some(ret) = &func.ret {
+            self.ty(ret);
+        }
+
+        if let Some(body) = &func.body {
+            self.func_body(body);
+        }
+
+        self.locals.truncate(locals);
+    }
+
+    fn func_body(&mut self, body: &FuncBody) {
+        match &body.kind {
+            FuncBodyKind::Block(block) => self.block(block),
+            FuncBodyKind::Expr(expr) => self.expr(expr),
+        }
+    }
+
+    fn var(&mut self, var: &VariableDecl) {
+        if let Some(ty) = &var.ty {
+            self.ty(ty);
+        }
+
+        if let Some(init) = &var.init {
+            self.expr(init);
+        }
+
+        if let VariableName::Identifier(name) = &var.name {
+            self.locals.push(name.clone());
+        }
+    }
+
+    fn struct_body(&mut self, body: &StructBody) {
+        for param in &body.params {
+            self.ty(&param.ty);
+        }
+    }
+
+    fn block(&mut self, block: &Block) {
+        let locals = self.locals.len();
+
+        for statement in &block.statements {
+            self.statement(statement);
+        }
+
+        self.locals.truncate(locals);
+    }
+
+    fn statement(&mut self, statement: &Statement) {
+        match &statement.kind {
+            StatementKind::Decl(var) => self.var(var),
+            StatementKind::Expr(expr) => self.expr(expr),
+            StatementKind::If(stmt) => {
+                for condition in &stmt.conditions {
+                    let locals = self.locals.len();
+
+                    if let Some(clause) = &condition.condition {
+                        match &clause.kind {
+                            IfClauseKind::Expr(expr) => self.expr(expr),
+                            IfClauseKind::LetMatch(clause) => {
+                                self.expr(&clause.value);
+                                self.bind(&clause.pat);
+                            }
+                        }
+                    }
+
+                    self.block(&condition.block);
+
+                    self.locals.truncate(locals);
+                }
+            }
+            StatementKind::LetMatchElse(stmt) => {
+                self.expr(&stmt.clause.value);
+                self.block(&stmt.block);
+                self.bind(&stmt.clause.pat);
+            }
+            StatementKind::Match(stmt) => {
+                self.expr(&stmt.value);
+
+                for clause in &stmt.clauses {
+                    let locals = self.locals.len();
+
+                    self.bind(&clause.pat);
+                    match &clause.block.kind {
+                        MatchBlockKind::Statement(statement) => self.statement(statement),
+                        MatchBlockKind::Block(block) => self.block(block),
+                    }
+
+                    self.locals.truncate(locals);
+                }
+            }
+            StatementKind::Return(stmt) => {
+                if let Some(value) = &stmt.value {
+                    self.expr(value);
+                }
+                if let Some(condition) = &stmt.condition {
+                    self.expr(condition);
+                }
+            }
+            StatementKind::For(stmt) => {
+                let locals = self.locals.len();
+
+                if let Some(init) = &stmt.init {
+                    self.var(init);
+                }
+                if let Some(condition) = &stmt.condition {
+                    self.expr(condition);
+                }
+                if let Some(step) = &stmt.step {
+                    self.expr(step);
+                }
+                self.block(&stmt.block);
+
+                self.locals.truncate(locals);
+            }
+            StatementKind::ForIn(stmt) => {
+                self.expr(&stmt.value);
+
+                let locals = self.locals.len();
+
+                if let VariableName::Identifier(name) = &stmt.name {
+                    self.locals.push(name.clone());
+                }
+                self.block(&stmt.block);
+
+                self.locals.truncate(locals);
+            }
+            StatementKind::While(stmt) => {
+                self.expr(&stmt.condition);
+                self.block(&stmt.block);
+            }
+            StatementKind::Loop(stmt) => self.block(&stmt.block),
+            StatementKind::Label(stmt) => {
+                if let Some(block) = &stmt.block {
+                    self.block(block);
+                }
+            }
+            StatementKind::Goto(_) => {}
+            StatementKind::Break(stmt) => {
+                if let Some(condition) = &stmt.condition {
+                    self.expr(condition);
+                }
+            }
+            StatementKind::Error => {}
+        }
+    }
+
+    /// Adds every name a pattern binds to the locals
+    fn bind(&mut self, pat: &Pattern) {
+        match &pat.kind {
+            PatternKind::Value { is_mut: _, name } => self.locals.push(name.clone()),
+            PatternKind::Initializer { list, .. } => match &list.kind {
+                InitializerPatternKind::Expr(values) => {
+                    for value in values {
+                        self.bind(value);
+                    }
+                }
+                InitializerPatternKind::Named(values) => {
+                    for value in values {
+                        self.bind(&value.value);
+                    }
+                }
+                InitializerPatternKind::Empty => {}
+            },
+            PatternKind::Or(values) => {
+                for value in values {
+                    self.bind(value);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn expr(&mut self, expr: &Expr) {
+        match &expr.kind {
+            ExprKind::Invoke { value, params } => {
+                // Formatting functions are builtin unless something shadows them
+                match format_func(value, self.scope) {
+                    Some(func) if !self.locals.contains(&func.path[0]) => {}
+                    _ => self.expr(value),
+                }
+                for param in params {
+                    self.expr(param);
+                }
+            }
+            ExprKind::Index { value, index } => {
+                self.expr(value);
+                self.expr(index);
+            }
+            ExprKind::Field {
+                value, generics, ..
+            } => {
+                self.expr(value);
+                self.generics_instance(generics);
+            }
+            ExprKind::BinOp { lhs, op: _, rhs } => {
+                self.expr(lhs);
+                self.expr(rhs);
+            }
+            ExprKind::Cast { value, ty } => {
+                self.expr(value);
+                self.ty(ty);
+            }
+            ExprKind::UnaryOp { op: _, value } => self.expr(value),
+            ExprKind::Assign {
+                target,
+                op: _,
+                value,
+            } => {
+                self.expr(target);
+                self.expr(value);
+            }
+            ExprKind::Variable { path, generics } => {
+                if path.path.len() != 1 || !self.locals.contains(&path.path[0]) {
+                    self.path(path);
+                }
+                self.generics_instance(generics);
+            }
+            ExprKind::Initializer {
+                path,
+                generics,
+                list,
+            } => {
+                self.path(path);
+                self.generics_instance(generics);
+                self.initializer_list(list);
+            }
+            ExprKind::AnonStructInitializer { list } => self.initializer_list(list),
+            ExprKind::Array(ArrayInitializer::List(values)) => {
+                for value in values {
+                    self.expr(value);
+                }
+            }
+            ExprKind::Array(ArrayInitializer::Repeat { value, len: _ }) => self.expr(value),
+            ExprKind::Lambda {
+                params,
+                captures,
+                body,
+            } => {
+                let mut locals = vec![];
+
+                if let Some(captures) = captures {
+                    for capture in &captures.captures {
+                        locals.push(capture.name.clone());
+                    }
+                }
+
+                if let Some(params) = params {
+                    for param in &params.params {
+                        locals.push(param.name.clone());
+                    }
+                }
+
+                let outer = std::mem::replace(&mut self.locals, locals);
+                self.func_body(body);
+                self.locals = outer;
+            }
+            ExprKind::SizeofType(ty) => self.ty(ty),
+            ExprKind::SizeofValue(value) => self.expr(value),
+            ExprKind::Primitive(_) | ExprKind::This | ExprKind::Nullptr | ExprKind::Discard => {}
+        }
+    }
+
+    fn initializer_list(&mut self, list: &InitializerList) {
+        match &list.kind {
+            InitializerKind::Expr(values) => {
+                for value in values {
+                    self.expr(value);
+                }
+            }
+            InitializerKind::Named { values, default } => {
+                for value in values {
+                    self.expr(&value.value);
+                }
+                if let Some(default) = default {
+                    self.expr(&default.value);
+                }
+            }
+            InitializerKind::Empty => {}
+        }
+    }
+
+    fn generics_instance(&mut self, generics: &Option<GenericsInstance>) {
+        if let Some(generics) = generics {
+            for ty in &generics.params {
+                self.ty(ty);
+            }
+        }
+    }
+
+    fn ty(&mut self, ty: &Type) {
+        self.scope.resolve_type(ty, &self.generics, self.errors);
+    }
+
+    fn path(&mut self, path: &IdentPath) {
+        // Generic types are only known once they're instantiated
+        if self.generics.contains(&path.path[0]) {
+            return;
+        }
+
+        if let Err(error) = self.scope.resolve(path) {
+            self.errors.push(*error);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use crate::{
+        parse_tree::parse::error::ParserError,
+        semantic_model::{error::SemanticError, fixture::analyze},
+    };
+
+    fn resolve(srcs: &[&str]) -> Result<Vec<SemanticError>, ParserError> {
+        return analyze(srcs, |_, _, _, _| {});
+    }
+
+    #[test]
+    fn usings() -> Result<(), ParserError> {
+        let errors = resolve(&[
+            "namespace Std::Random; pub func Next(): i32 => 4;",
+            "namespace Wawa; struct Test { x: i32 }",
+            "namespace Wawa::Test;
+            using Std::Random;
+            using Std::Random = Rand;
+            func Main(t: Test): i32 {
+                let x = Rand::Next();
+                return Random::Next() + x;
+            }",
+        ])?;
+
+        assert_eq!(errors, vec![]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn unknown_and_ambiguous() -> Result<(), ParserError> {
+        let errors = resolve(&[
+            "namespace A; func X();",
+            "namespace B; func X();",
+            "using A::X; using B::X; func Main() { X(); Y(); }",
+        ])?;
+
+        let [SemanticError::AmbiguousPath { path, candidates }, SemanticError::UnknownPath { path: unknown }] =
+            &errors[..]
+        else {
+            panic!("expected an ambiguous and an unknown path, got {errors:?}");
+        };
+
+        assert_eq!(path.path, vec![Arc::from("X")]);
+        assert_eq!(candidates.len(), 2);
+        assert_eq!(unknown.path, vec![Arc::from("Y")]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn operator_arity() -> Result<(), ParserError> {
+        let errors = resolve(&[
+            "where T : operator +<T, T>, operator _|_:<T>, operator ~_, operator _-_:<T, T>;
+            func F();",
+        ])?;
+
+        let [SemanticError::GenericArity {
+            slice,
+            expected: 1,
+            found: 2,
+        }] = &errors[..]
+        else {
+            panic!("expected a single generic arity error, got {errors:?}");
+        };
+        assert_eq!(&*slice.value(), "operator _-_:<T, T>");
+
+        Ok(())
+    }
+}
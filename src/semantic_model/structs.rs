@@ -0,0 +1,246 @@
+use std::{collections::HashMap, sync::Arc};
+
+use crate::parse_tree::decl::{EnumDeclKind, StructBody, StructDeclKind};
+
+use super::{
+    error::SemanticError,
+    namespace::{GlobalSymbol, Namespace, Scope},
+    symbol::SymbolKind,
+    types::ResolvedType,
+};
+
+/// Finds structs, unions and enums that contain themselves by value
+pub fn check_struct_sizes(scopes: &[Scope], errors: &mut Vec<SemanticError>) {
+    let Some(scope) = scopes.first() else {
+        return;
+    };
+
+    let mut decls = HashMap::new();
+    collect_decls(&scope.tree.root, scopes, &mut decls);
+
+    let mut checker = SizeChecker {
+        decls: &decls,
+        stack: vec![],
+        reported: vec![],
+        errors,
+    };
+
+    let mut roots: Vec<_> = decls.iter().collect();
+    roots.sort_by(|a, b| a.0.cmp(b.0));
+
+    for (path, decl) in roots {
+        let root = ResolvedType::Declared {
+            path: path.clone(),
+            generics: decl
+                .generics
+                .iter()
+                .map(|it| ResolvedType::Generic(it.clone()))
+                .collect(),
+        };
+
+        checker.visit(root, decl.symbol);
+    }
+}
+
+/// The by-value layout of a single declaration
+struct SizedDecl<'a> {
+    symbol: &'a GlobalSymbol,
+    generics: Vec<Arc<str>>,
+    fields: Vec<ResolvedType>,
+}
+
+fn collect_decls<'a>(
+    namespace: &'a Namespace,
+    scopes: &[Scope],
+    decls: &mut HashMap<Vec<Arc<str>>, SizedDecl<'a>>,
+) {
+    for child in namespace.children.values() {
+        collect_decls(child, scopes, decls);
+    }
+
+    for symbol in namespace.symbols.values() {
+        let bodies: Vec<&StructBody> = match &symbol.symbol.kind {
+            SymbolKind::Struct(st) => match &st.kind {
+                StructDeclKind::Value(body) => vec![body],
+                StructDeclKind::Wrapper(_) => vec![],
+            },
+            SymbolKind::Union(un) => vec![&un.body],
+            SymbolKind::Enum(en) => match &en.kind {
+                EnumDeclKind::Value(body) => vec![body],
+                EnumDeclKind::Int { .. } => continue,
+            },
+            _ => continue,
+        };
+
+        let generics: Vec<_> = match &symbol.symbol.generics {
+            Some(generics) => generics.tys.iter().map(|it| it.name.clone()).collect(),
+            None => vec![],
+        };
+
+        let scope = &scopes[symbol.file];
+
+        // Unresolved types were already reported by resolve_paths
+        let mut ignored = vec![];
+        let mut fields: Vec<_> = bodies
+            .iter()
+            .flat_map(|it| &it.params)
+            .map(|it| scope.resolve_type(&it.ty, &generics, &mut ignored))
+            .collect();
+
+        if let SymbolKind::Struct(st) = &symbol.symbol.kind
+            && let StructDeclKind::Wrapper(ty) = &st.kind
+        {
+            fields.push(scope.resolve_type(ty, &generics, &mut ignored));
+        }
+
+        decls.insert(
+            symbol.symbol.path.clone(),
+            SizedDecl {
+                symbol,
+                generics,
+                fields,
+            },
+        );
+    }
+}
+
+struct SizeChecker<'a, 'e> {
+    decls: &'a HashMap<Vec<Arc<str>>, SizedDecl<'a>>,
+    stack: Vec<ResolvedType>,
+    reported: Vec<Vec<Vec<Arc<str>>>>,
+    errors: &'e mut Vec<SemanticError>,
+}
+
+/// Deep enough for any sane nesting of generic types
+const MAX_DEPTH: usize = 64;
+
+impl SizeChecker<'_, '_> {
+    fn visit(&mut self, ty: ResolvedType, root: &GlobalSymbol) {
+        match &ty {
+            ResolvedType::Array { ty, len: _ } | ResolvedType::Option(ty) => {
+                self.visit(*ty.clone(), root);
+            }
+            ResolvedType::Struct(fields) => {
+                for field in fields {
+                    self.visit(field.ty.clone(), root);
+                }
+            }
+            ResolvedType::Declared { path, generics } => {
+                let Some(decl) = self.decls.get(path) else {
+                    return;
+                };
+
+                if let Some(pos) = self.stack.iter().position(|it| *it == ty) {
+                    self.report(pos, root);
+                    return;
+                }
+
+                if self.stack.len() >= MAX_DEPTH {
+                    self.report(0, root);
+                    return;
+                }
+
+                self.stack.push(ty.clone());
+
+                for field in &decl.fields {
+                    self.visit(field.substitute(&decl.generics, generics), root);
+                }
+
+                self.stack.pop();
+            }
+            _ => {}
+        }
+    }
+
+    fn report(&mut self, pos: usize, root: &GlobalSymbol) {
+        let mut cycle: Vec<_> = self.stack[pos..]
+            .iter()
+            .filter_map(|it| match it {
+                ResolvedType::Declared { path, .. } => Some(path.clone()),
+                _ => None,
+            })
+            .collect();
+
+        let mut key = cycle.clone();
+        key.sort();
+        key.dedup();
+        if self.reported.contains(&key) {
+            return;
+        }
+        self.reported.push(key);
+
+        cycle.push(cycle[0].clone());
+
+        self.errors.push(SemanticError::InfiniteSize {
+            slice: root.symbol.slice.clone(),
+            cycle,
+        });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use crate::{
+        parse_tree::parse::error::ParserError,
+        semantic_model::{error::SemanticError, fixture::analyze},
+    };
+
+    use super::check_struct_sizes;
+
+    fn check(src: &str) -> Result<Vec<SemanticError>, ParserError> {
+        return analyze(&[src], |scopes, _, _, errors| {
+            check_struct_sizes(scopes, errors)
+        });
+    }
+
+    #[test]
+    fn sized() -> Result<(), ParserError> {
+        let errors = check(
+            "where T; struct Wrap { value: T }
+            struct List { value: i32, next: *List, nested: Wrap:<Wrap:<i32> > }",
+        )?;
+
+        assert_eq!(errors, vec![]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn infinite() -> Result<(), ParserError> {
+        let errors = check(
+            "where T; struct Wrap { value: T }
+            struct A { b: [B, 2] }
+            struct B { a: ?Wrap:<A> }",
+        )?;
+
+        let [SemanticError::InfiniteSize { slice: _, cycle }] = &errors[..] else {
+            panic!("expected a single infinite struct, got {errors:?}");
+        };
+
+        let a: Vec<Arc<str>> = vec!["A".into()];
+        let b: Vec<Arc<str>> = vec!["B".into()];
+        let wrap: Vec<Arc<str>> = vec!["Wrap".into()];
+        assert_eq!(cycle, &vec![a.clone(), b, wrap, a]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn generic_arity() -> Result<(), ParserError> {
+        let errors = check("where T; struct Wrap { value: T } struct A { w: Wrap }")?;
+
+        let [SemanticError::GenericArity {
+            slice,
+            expected: 1,
+            found: 0,
+        }] = &errors[..]
+        else {
+            panic!("expected a single generic arity error, got {errors:?}");
+        };
+        assert_eq!(&*slice.value(), "Wrap");
+
+        Ok(())
+    }
+}
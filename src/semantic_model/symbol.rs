@@ -0,0 +1,198 @@
+use std::{collections::HashMap, sync::Arc};
+
+use crate::{
+    parse_tree::{
+        decl::{
+            DeclLvl1Kind, DeclLvl2, DeclLvl2Kind, DeclModifier, EnumDecl, FunctionDecl,
+            GenericsDecl, ImplDecl, StructDecl, TraitDecl, UnionDecl, VariableDecl,
+        },
+        statement::VariableName,
+        ParseTree,
+    },
+    string::StringSlice,
+};
+
+use super::error::SemanticError;
+
+/// Every symbol declared by a single file, no info from other files
+#[derive(Debug, Clone, PartialEq)]
+pub struct SymbolTable {
+    pub namespace: Vec<Arc<str>>,
+    pub symbols: Vec<Symbol>,
+    pub impls: Vec<DeclModifier<ImplDecl>>,
+    index: HashMap<Arc<str>, usize>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Symbol {
+    pub slice: StringSlice,
+    pub name: Arc<str>,
+    pub path: Vec<Arc<str>>,
+    pub is_pub: bool,
+    pub generics: Option<GenericsDecl>,
+    pub kind: SymbolKind,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SymbolKind {
+    Struct(StructDecl),
+    Enum(EnumDecl),
+    Union(UnionDecl),
+    Trait(TraitDecl),
+    Function(FunctionDecl),
+    Variable(VariableDecl),
+}
+
+impl SymbolTable {
+    pub fn build(tree: &ParseTree, errors: &mut Vec<SemanticError>) -> Self {
+        let namespace = match &tree.namespace {
+            Some(namespace) => namespace.path.path.clone(),
+            None => vec![],
+        };
+
+        let mut table = Self {
+            namespace,
+            symbols: vec![],
+            impls: vec![],
+            index: HashMap::new(),
+        };
+
+        for decl in &tree.body {
+            let (name, kind) = match &decl.value.kind {
+                DeclLvl1Kind::Struct(st) => (st.name.clone(), SymbolKind::Struct(st.clone())),
+                DeclLvl1Kind::Enum(en) => (en.name.clone(), SymbolKind::Enum(en.clone())),
+                DeclLvl1Kind::Union(un) => (un.name.clone(), SymbolKind::Union(un.clone())),
+                DeclLvl1Kind::Trait(tr) => (tr.name.clone(), SymbolKind::Trait(tr.clone())),
+                DeclLvl1Kind::Impl(im) => {
+                    table.impls.push(DeclModifier {
+                        slice: decl.slice.clone(),
+                        attrs: decl.attrs.clone(),
+                        generics: decl.generics.clone(),
+                        is_pub: decl.is_pub,
+                        value: im.clone(),
+                    });
+                    continue;
+                }
+                DeclLvl1Kind::Lvl2(lvl2) => {
+                    let Some(value) = Self::lvl_2_symbol(lvl2) else {
+                        continue;
+                    };
+                    value
+                }
+                DeclLvl1Kind::Error => continue,
+            };
+
+            table.insert(
+                Symbol {
+                    slice: decl.slice.clone(),
+                    path: table.path_of(&name),
+                    name,
+                    is_pub: decl.is_pub,
+                    generics: decl.generics.clone(),
+                    kind,
+                },
+                errors,
+            );
+        }
+
+        return table;
+    }
+
+    fn lvl_2_symbol(decl: &DeclLvl2) -> Option<(Arc<str>, SymbolKind)> {
+        match &decl.kind {
+            DeclLvl2Kind::Function(func) => {
+                return Some((func.name.clone(), SymbolKind::Function(func.clone())));
+            }
+            DeclLvl2Kind::Variable(var) => {
+                let VariableName::Identifier(name) = &var.name else {
+                    return None;
+                };
+                return Some((name.clone(), SymbolKind::Variable(var.clone())));
+            }
+        }
+    }
+
+    fn path_of(&self, name: &Arc<str>) -> Vec<Arc<str>> {
+        let mut path = self.namespace.clone();
+        path.push(name.clone());
+        return path;
+    }
+
+    fn insert(&mut self, symbol: Symbol, errors: &mut Vec<SemanticError>) {
+        if let Some(idx) = self.index.get(&symbol.name) {
+            errors.push(SemanticError::DuplicateSymbol {
+                name: symbol.name.clone(),
+                first: self.symbols[*idx].slice.clone(),
+                second: symbol.slice,
+            });
+            return;
+        }
+
+        self.index.insert(symbol.name.clone(), self.symbols.len());
+        self.symbols.push(symbol);
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Symbol> {
+        return self.index.get(name).map(|idx| &self.symbols[*idx]);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        parse_tree::parse::{error::ParserError, parse_root},
+        semantic_model::error::SemanticError,
+        tokenizer::Tokenizer,
+    };
+
+    use super::{SymbolKind, SymbolTable};
+
+    type TestResult = Result<(), ParserError>;
+
+    #[test]
+    fn namespaced_symbols() -> TestResult {
+        const SRC: &str = "namespace A::B; where T; pub struct X { x: T } func Y(): i32 => 0;";
+        let tree = parse_root(&mut Tokenizer::new(SRC.into()))?;
+
+        let mut errors = vec![];
+        let table = SymbolTable::build(&tree, &mut errors);
+
+        assert!(errors.is_empty());
+
+        let x = table.get("X").unwrap();
+        assert_eq!(x.path, vec!["A".into(), "B".into(), "X".into()]);
+        assert!(x.is_pub);
+        assert!(x.generics.is_some());
+        assert!(matches!(x.kind, SymbolKind::Struct(_)));
+
+        let y = table.get("Y").unwrap();
+        assert!(!y.is_pub);
+        assert!(matches!(y.kind, SymbolKind::Function(_)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn duplicate_symbols() -> TestResult {
+        const SRC: &str = "struct X { x: i32 } func X();";
+        let tree = parse_root(&mut Tokenizer::new(SRC.into()))?;
+
+        let mut errors = vec![];
+        let table = SymbolTable::build(&tree, &mut errors);
+
+        assert_eq!(table.symbols.len(), 1);
+        let [SemanticError::DuplicateSymbol {
+            name,
+            first,
+            second,
+        }] = &errors[..]
+        else {
+            panic!("expected a single duplicate symbol, got {errors:?}");
+        };
+        assert_eq!(&**name, "X");
+        assert_eq!(&*first.value(), "struct X { x: i32 }");
+        assert_eq!(&*second.value(), "func X();");
+
+        Ok(())
+    }
+}
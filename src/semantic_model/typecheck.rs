@@ -0,0 +1,2064 @@
+use std::sync::Arc;
+
+use crate::{
+    parse_tree::{
+        decl::{
+            DeclLvl1, DeclLvl1Kind, DeclLvl2, DeclLvl2Kind, DeclModifier, EnumDeclKind, FuncBody,
+            FuncBodyKind, FunctionDecl, GenericsDecl, IntEnumType, StructBody, StructDeclKind,
+            VariableDecl,
+        },
+        expr::{
+            AccessKind, ArrayInitializer, AssignOp, BinOp, Expr, ExprKind, GenericsInstance,
+            InitializerKind, InitializerList, PrimitiveExpr, PrimitiveExprKind, UnaryOp,
+        },
+        pattern::{InitializerPatternKind, Pattern, PatternKind},
+        statement::{
+            Block, IfClauseKind, MatchBlockKind, Statement, StatementKind, VariableModifier,
+            VariableName,
+        },
+        types::{RefKind, Type},
+        IdentPath,
+    },
+    string::StringSlice,
+    tokenizer::token::{Keyword, Number, NumberValue},
+};
+
+use super::{
+    error::SemanticError,
+    format::{format_func, parse_format, FormatArg, FormatSpec, DISPLAY_TRAIT},
+    import::SourceFile,
+    namespace::{GlobalSymbol, Scope},
+    symbol::{SymbolKind, SymbolTable},
+    types::{ResolvedField, ResolvedType},
+};
+
+/// Infers the type of every expression and checks it against the type it's used as
+pub fn check_types(
+    scopes: &[Scope],
+    tables: &[SymbolTable],
+    files: &[SourceFile],
+    errors: &mut Vec<SemanticError>,
+) {
+    for (scope, source) in scopes.iter().zip(files) {
+        let mut checker = TypeChecker {
+            scope,
+            scopes,
+            tables,
+            generics: vec![],
+            locals: vec![],
+            this: None,
+            this_value: None,
+            ret: ResolvedType::Unit,
+            errors,
+        };
+
+        for decl in &source.tree.body {
+            checker.lvl_1_decl(decl);
+        }
+    }
+}
+
+struct Local {
+    name: Arc<str>,
+    ty: ResolvedType,
+    is_mut: bool,
+}
+
+struct TypeChecker<'a, 'e> {
+    scope: &'a Scope<'a>,
+    scopes: &'a [Scope<'a>],
+    tables: &'a [SymbolTable],
+    generics: Vec<Arc<str>>,
+    locals: Vec<Local>,
+    /// The type the enclosing impl block is for
+    this: Option<ResolvedType>,
+    /// The type of `this` in the enclosing method, and whether it's mutable
+    this_value: Option<(ResolvedType, bool)>,
+    ret: ResolvedType,
+    errors: &'e mut Vec<SemanticError>,
+}
+
+fn generic_names(generics: &Option<GenericsDecl>) -> Vec<Arc<str>> {
+    return match generics {
+        Some(generics) => generics.tys.iter().map(|it| it.name.clone()).collect(),
+        None => vec![],
+    };
+}
+
+/// Strips references, fields and indices are reachable through them
+fn deref(ty: &ResolvedType) -> &ResolvedType {
+    return match ty {
+        ResolvedType::Ref {
+            ref_kind: RefKind::Mutable | RefKind::Immutable,
+            ty,
+        } => deref(ty),
+        _ => ty,
+    };
+}
+
+fn is_number(expr: &Expr) -> bool {
+    return match &expr.kind {
+        ExprKind::Primitive(PrimitiveExpr {
+            kind: PrimitiveExprKind::Number(_),
+            ..
+        }) => true,
+        ExprKind::UnaryOp {
+            op: UnaryOp::Add | UnaryOp::Sub,
+            value,
+        } => is_number(value),
+        _ => false,
+    };
+}
+
+impl TypeChecker<'_, '_> {
+    fn push_generics(&mut self, generics: &Option<GenericsDecl>) -> usize {
+        let len = self.generics.len();
+        self.generics.extend(generic_names(generics));
+        return len;
+    }
+
+    fn lvl_1_decl(&mut self, decl: &DeclModifier<DeclLvl1>) {
+        let generics = self.push_generics(&decl.generics);
+        let locals = self.locals.len();
+
+        match &decl.value.kind {
+            DeclLvl1Kind::Enum(en) => {
+                if let EnumDeclKind::Int { ty, body } = &en.kind {
+                    let ty = match ty {
+                        IntEnumType::U8 => ResolvedType::U8,
+                        IntEnumType::I8 => ResolvedType::I8,
+                        IntEnumType::U16 => ResolvedType::U16,
+                        IntEnumType::I16 => ResolvedType::I16,
+                        IntEnumType::U32 => ResolvedType::U32,
+                        IntEnumType::I32 => ResolvedType::I32,
+                        IntEnumType::U64 => ResolvedType::U64,
+                        IntEnumType::I64 => ResolvedType::I64,
+                    };
+
+                    for param in &body.params {
+                        if let Some(value) = &param.value {
+                            self.expect(value, &ty);
+                        }
+                    }
+                }
+            }
+            DeclLvl1Kind::Trait(tr) => {
+                for decl in &tr.body.decls {
+                    self.lvl_2_decl(decl);
+                }
+            }
+            DeclLvl1Kind::Impl(im) => {
+                let this = self.resolve(&im.ty);
+                let outer = self.this.replace(this);
+
+                for decl in &im.body.decls {
+                    self.lvl_2_decl(decl);
+                }
+
+                self.this = outer;
+            }
+            DeclLvl1Kind::Lvl2(lvl2) => self.lvl_2_decl_raw(lvl2),
+            DeclLvl1Kind::Struct(_) | DeclLvl1Kind::Union(_) | DeclLvl1Kind::Error => {}
+        }
+
+        self.locals.truncate(locals);
+        self.generics.truncate(generics);
+    }
+
+    fn lvl_2_decl(&mut self, decl: &DeclModifier<DeclLvl2>) {
+        let generics = self.push_generics(&decl.generics);
+
+        self.lvl_2_decl_raw(&decl.value);
+
+        self.generics.truncate(generics);
+    }
+
+    fn lvl_2_decl_raw(&mut self, decl: &DeclLvl2) {
+        match &decl.kind {
+            DeclLvl2Kind::Function(func) => self.func(func),
+            DeclLvl2Kind::Variable(var) => self.var(var),
+        }
+    }
+
+    fn func(&mut self, func: &FunctionDecl) {
+        let Some(body) = &func.body else {
+            return;
+        };
+
+        let locals = self.locals.len();
+
+        let this_value = func.this_param.as_ref().map(|this| {
+            let ty = match this.ref_kind {
+                Some(ref_kind) => ResolvedType::Ref {
+                    ref_kind,
+                    ty: Box::new(ResolvedType::This),
+                },
+                None => ResolvedType::This,
+            };
+
+            (ty, this.is_mut || this.ref_kind == Some(RefKind::Mutable))
+        });
+
+        for param in &func.params {
+            let ty = self.resolve(&param.ty);
+            self.locals.push(Local {
+                name: param.name.clone(),
+                ty,
+                is_mut: param.is_mut,
+            });
+        }
+
+        let ret = match &func.ret {
+            Some(ret) => self.resolve(ret),
+            None => ResolvedType::Unit,
+        };
+
+        let outer_ret = std::mem::replace(&mut self.ret, ret);
+        let outer_this = std::mem::replace(&mut self.this_value, this_value);
+
+        self.func_body(body);
+
+        self.this_value = outer_this;
+        self.ret = outer_ret;
+        self.locals.truncate(locals);
+    }
+
+    fn func_body(&mut self, body: &FuncBody) {
+        match &body.kind {
+            FuncBodyKind::Block(block) => self.block(block),
+            FuncBodyKind::Expr(expr) => {
+                let ret = self.ret.clone();
+
+                // Functions without a return type only evaluate the expression
+                if ret == ResolvedType::Unit {
+                    self.expr(expr, None);
+                } else {
+                    self.expect(expr, &ret);
+                }
+            }
+        }
+    }
+
+    fn var(&mut self, var: &VariableDecl) {
+        let ty = match (&var.ty, &var.init) {
+            (Some(ty), init) => {
+                let ty = self.resolve(ty);
+                if let Some(init) = init {
+                    self.expect(init, &ty);
+                }
+                ty
+            }
+            (None, Some(init)) => self.expr(init, None),
+            (None, None) => ResolvedType::Unknown,
+        };
+
+        if let VariableName::Identifier(name) = &var.name {
+            self.locals.push(Local {
+                name: name.clone(),
+                ty,
+                is_mut: var.modifier == VariableModifier::Mut,
+            });
+        }
+    }
+
+    fn block(&mut self, block: &Block) {
+        let locals = self.locals.len();
+
+        for statement in &block.statements {
+            self.statement(statement);
+        }
+
+        self.locals.truncate(locals);
+    }
+
+    fn statement(&mut self, statement: &Statement) {
+        match &statement.kind {
+            StatementKind::Decl(var) => self.var(var),
+            StatementKind::Expr(expr) => {
+                self.expr(expr, None);
+            }
+            StatementKind::If(stmt) => {
+                for condition in &stmt.conditions {
+                    let locals = self.locals.len();
+
+                    if let Some(clause) = &condition.condition {
+                        match &clause.kind {
+                            IfClauseKind::Expr(expr) => self.expect(expr, &ResolvedType::Bool),
+                            IfClauseKind::LetMatch(clause) => {
+                                self.expr(&clause.value, None);
+                                self.bind(&clause.pat);
+                            }
+                        }
+                    }
+
+                    self.block(&condition.block);
+
+                    self.locals.truncate(locals);
+                }
+            }
+            StatementKind::LetMatchElse(stmt) => {
+                self.expr(&stmt.clause.value, None);
+                self.block(&stmt.block);
+                self.bind(&stmt.clause.pat);
+            }
+            StatementKind::Match(stmt) => {
+                self.expr(&stmt.value, None);
+
+                for clause in &stmt.clauses {
+                    let locals = self.locals.len();
+
+                    self.bind(&clause.pat);
+                    match &clause.block.kind {
+                        MatchBlockKind::Statement(statement) => self.statement(statement),
+                        MatchBlockKind::Block(block) => self.block(block),
+                    }
+
+                    self.locals.truncate(locals);
+                }
+            }
+            StatementKind::Return(stmt) => {
+                let ret = self.ret.clone();
+
+                match &stmt.value {
+                    Some(value) => self.expect(value, &ret),
+                    None if !ret.accepts(&ResolvedType::Unit) => {
+                        self.errors.push(SemanticError::TypeMismatch {
+                            slice: stmt.slice.clone(),
+                            expected: ret,
+                            found: ResolvedType::Unit,
+                        });
+                    }
+                    None => {}
+                }
+
+                if let Some(condition) = &stmt.condition {
+                    self.expect(condition, &ResolvedType::Bool);
+                }
+            }
+            StatementKind::For(stmt) => {
+                let locals = self.locals.len();
+
+                if let Some(init) = &stmt.init {
+                    self.var(init);
+                }
+                if let Some(condition) = &stmt.condition {
+                    self.expect(condition, &ResolvedType::Bool);
+                }
+                if let Some(step) = &stmt.step {
+                    self.expr(step, None);
+                }
+                self.block(&stmt.block);
+
+                self.locals.truncate(locals);
+            }
+            StatementKind::ForIn(stmt) => {
+                let ty = match self.expr(&stmt.value, None) {
+                    ResolvedType::Range(ty) => *ty,
+                    ResolvedType::Unknown => ResolvedType::Unknown,
+                    ty => {
+                        self.errors.push(SemanticError::NotIterable {
+                            slice: stmt.value.slice.clone(),
+                            ty,
+                        });
+                        ResolvedType::Unknown
+                    }
+                };
+
+                let locals = self.locals.len();
+
+                if let VariableName::Identifier(name) = &stmt.name {
+                    self.locals.push(Local {
+                        name: name.clone(),
+                        ty,
+                        is_mut: stmt.is_mut,
+                    });
+                }
+                self.block(&stmt.block);
+
+                self.locals.truncate(locals);
+            }
+            StatementKind::While(stmt) => {
+                self.expect(&stmt.condition, &ResolvedType::Bool);
+                self.block(&stmt.block);
+            }
+            StatementKind::Loop(stmt) => self.block(&stmt.block),
+            StatementKind::Label(stmt) => {
+                if let Some(block) = &stmt.block {
+                    self.block(block);
+                }
+            }
+            StatementKind::Goto(_) => {}
+            StatementKind::Break(stmt) => {
+                if let Some(condition) = &stmt.condition {
+                    self.expect(condition, &ResolvedType::Bool);
+                }
+            }
+            StatementKind::Error => {}
+        }
+    }
+
+    /// Adds every name a pattern binds to the locals, patterns aren't typed yet
+    fn bind(&mut self, pat: &Pattern) {
+        match &pat.kind {
+            PatternKind::Value { is_mut, name } => self.locals.push(Local {
+                name: name.clone(),
+                ty: ResolvedType::Unknown,
+                is_mut: *is_mut,
+            }),
+            PatternKind::Initializer { list, .. } => match &list.kind {
+                InitializerPatternKind::Expr(values) => {
+                    for value in values {
+                        self.bind(value);
+                    }
+                }
+                InitializerPatternKind::Named(values) => {
+                    for value in values {
+                        self.bind(&value.value);
+                    }
+                }
+                InitializerPatternKind::Empty => {}
+            },
+            PatternKind::Or(values) => {
+                for value in values {
+                    self.bind(value);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Checks an expression against the type it's used as
+    fn expect(&mut self, expr: &Expr, expected: &ResolvedType) {
+        let found = self.expr(expr, Some(expected));
+
+        if !expected.accepts(&found) {
+            self.errors.push(SemanticError::TypeMismatch {
+                slice: expr.slice.clone(),
+                expected: expected.clone(),
+                found,
+            });
+        }
+    }
+
+    /// `expected` is only a hint for literals, the caller checks the result against it
+    fn expr(&mut self, expr: &Expr, expected: Option<&ResolvedType>) -> ResolvedType {
+        return match &expr.kind {
+            ExprKind::Invoke { value, params } => self.invoke(expr, value, params),
+            ExprKind::Index { value, index } => self.index(value, index),
+            ExprKind::Field {
+                value,
+                access,
+                field,
+                generics: _,
+            } => {
+                let ty = self.expr(value, None);
+                let (ty, coalesce) = self.field(expr, &ty, *access, field, false);
+
+                match coalesce {
+                    true => ResolvedType::Option(Box::new(ty)),
+                    false => ty,
+                }
+            }
+            ExprKind::BinOp { lhs, op, rhs } => self.bin_op(expr, lhs, *op, rhs, expected),
+            ExprKind::Cast { value, ty } => {
+                let to = self.resolve(ty);
+                let from = self.expr(value, Some(&to));
+
+                if !self.castable(&from, &to) {
+                    self.errors.push(SemanticError::InvalidCast {
+                        slice: expr.slice.clone(),
+                        from,
+                        to: to.clone(),
+                    });
+                }
+
+                to
+            }
+            ExprKind::UnaryOp { op, value } => self.unary_op(expr, *op, value, expected),
+            ExprKind::Assign { target, op, value } => self.assign(expr, target, *op, value),
+            ExprKind::Variable { path, generics: _ } => self.variable(path),
+            ExprKind::Initializer {
+                path,
+                generics,
+                list,
+            } => self.initializer(path, generics, list),
+            ExprKind::AnonStructInitializer { list } => match &list.kind {
+                InitializerKind::Named { values, default } => {
+                    let mut fields = vec![];
+                    for value in values {
+                        fields.push(ResolvedField {
+                            name: value.name.clone(),
+                            ty: self.expr(&value.value, None),
+                        });
+                    }
+
+                    match default {
+                        Some(default) => {
+                            self.expr(&default.value, None);
+                            ResolvedType::Unknown
+                        }
+                        None => ResolvedType::Struct(fields),
+                    }
+                }
+                _ => {
+                    self.initializer_list(list);
+                    ResolvedType::Unknown
+                }
+            },
+            ExprKind::Array(array) => self.array(expr, array, expected),
+            ExprKind::Lambda {
+                params,
+                captures,
+                body,
+            } => {
+                let mut locals = vec![];
+
+                if let Some(captures) = captures {
+                    for capture in &captures.captures {
+                        let ty = match self.local(&capture.name) {
+                            Some(local) => local.ty.clone(),
+                            None => ResolvedType::Unknown,
+                        };
+
+                        locals.push(Local {
+                            name: capture.name.clone(),
+                            ty,
+                            is_mut: false,
+                        });
+                    }
+                }
+
+                let mut param_tys = vec![];
+                if let Some(params) = params {
+                    for param in &params.params {
+                        param_tys.push(ResolvedType::Unknown);
+                        locals.push(Local {
+                            name: param.name.clone(),
+                            ty: ResolvedType::Unknown,
+                            is_mut: param.is_mut,
+                        });
+                    }
+                }
+
+                let outer_locals = std::mem::replace(&mut self.locals, locals);
+                let outer_ret = std::mem::replace(&mut self.ret, ResolvedType::Unknown);
+
+                self.func_body(body);
+
+                self.ret = outer_ret;
+                self.locals = outer_locals;
+
+                ResolvedType::Func {
+                    params: param_tys,
+                    ret: Box::new(ResolvedType::Unknown),
+                }
+            }
+            ExprKind::SizeofType(_) => ResolvedType::Usize,
+            ExprKind::SizeofValue(value) => {
+                self.expr(value, None);
+                ResolvedType::Usize
+            }
+            ExprKind::Primitive(prim) => self.primitive(prim, expected),
+            ExprKind::This => match &self.this_value {
+                Some((ty, _)) => ty.clone(),
+                None => ResolvedType::Unknown,
+            },
+            ExprKind::Nullptr => ResolvedType::Ref {
+                ref_kind: RefKind::Pointer,
+                ty: Box::new(ResolvedType::Unknown),
+            },
+            ExprKind::Discard => ResolvedType::Unknown,
+        };
+    }
+
+    /// Elements are checked against the element type of the expected array, or else the type of
+    /// the first element
+    fn array(
+        &mut self,
+        expr: &Expr,
+        array: &ArrayInitializer,
+        expected: Option<&ResolvedType>,
+    ) -> ResolvedType {
+        let element = match expected {
+            Some(ResolvedType::Array { ty, len: _ }) => Some(&**ty),
+            _ => None,
+        };
+
+        let (ty, len) = match array {
+            ArrayInitializer::List(values) => {
+                let mut ty = element.cloned();
+                for value in values {
+                    match &ty {
+                        Some(ty) => self.expect(value, ty),
+                        None => ty = Some(self.expr(value, None)),
+                    }
+                }
+                (ty.unwrap_or(ResolvedType::Unknown), values.len())
+            }
+            ArrayInitializer::Repeat { value, len } => match element {
+                Some(ty) => {
+                    self.expect(value, ty);
+                    (ty.clone(), *len)
+                }
+                None => (self.expr(value, None), *len),
+            },
+        };
+
+        // Reported here rather than as a mismatch, so the message can name both lengths
+        if let Some(
+            expected @ ResolvedType::Array {
+                ty: _,
+                len: expected_len,
+            },
+        ) = expected
+            && *expected_len != len
+        {
+            self.errors.push(SemanticError::ArrayLength {
+                slice: expr.slice.clone(),
+                expected: *expected_len,
+                found: len,
+            });
+            return expected.clone();
+        }
+
+        return ResolvedType::Array {
+            ty: Box::new(ty),
+            len,
+        };
+    }
+
+    fn primitive(&mut self, prim: &PrimitiveExpr, expected: Option<&ResolvedType>) -> ResolvedType {
+        return match &prim.kind {
+            PrimitiveExprKind::Number(number) => self.number(&prim.slice, number, false, expected),
+            PrimitiveExprKind::String(_) => ResolvedType::Ref {
+                ref_kind: RefKind::Immutable,
+                ty: Box::new(ResolvedType::Str),
+            },
+            PrimitiveExprKind::ByteString(bytes) | PrimitiveExprKind::CString(bytes) => {
+                ResolvedType::Array {
+                    ty: Box::new(ResolvedType::U8),
+                    len: bytes.len(),
+                }
+            }
+            PrimitiveExprKind::Char(_) => ResolvedType::Char,
+            PrimitiveExprKind::Bool(_) => ResolvedType::Bool,
+            PrimitiveExprKind::Default => match expected {
+                Some(ty) => ty.clone(),
+                None => ResolvedType::Unknown,
+            },
+            PrimitiveExprKind::Unit => ResolvedType::Unit,
+        };
+    }
+
+    /// Integer literals take any numeric type they're expected as, float literals only floats.
+    /// Without an expected type or a suffix they're `i32` and `f64`
+    fn number(
+        &mut self,
+        slice: &StringSlice,
+        number: &Number,
+        negative: bool,
+        expected: Option<&ResolvedType>,
+    ) -> ResolvedType {
+        let is_float = matches!(number.value, NumberValue::Float(_));
+
+        let suffix = number.suffix.map(|it| match it {
+            Keyword::U8 => ResolvedType::U8,
+            Keyword::I8 => ResolvedType::I8,
+            Keyword::U16 => ResolvedType::U16,
+            Keyword::I16 => ResolvedType::I16,
+            Keyword::U32 => ResolvedType::U32,
+            Keyword::I32 => ResolvedType::I32,
+            Keyword::U64 => ResolvedType::U64,
+            Keyword::I64 => ResolvedType::I64,
+            Keyword::Usize => ResolvedType::Usize,
+            Keyword::Isize => ResolvedType::Isize,
+            Keyword::F32 => ResolvedType::F32,
+            _ => ResolvedType::F64,
+        });
+
+        let ty = match (suffix, expected) {
+            (Some(suffix), _) => suffix,
+            (None, Some(ResolvedType::Option(ty))) => {
+                return self.number(slice, number, negative, Some(ty))
+            }
+            (None, Some(ty)) if ty.is_float() || (ty.is_integer() && !is_float) => ty.clone(),
+            (None, _) if is_float => ResolvedType::F64,
+            (None, _) => ResolvedType::I32,
+        };
+
+        let in_range = match (number.value, ty.int_range()) {
+            (NumberValue::Int(value), Some((min, max))) => {
+                let value = match negative {
+                    true => -(value as i128),
+                    false => value as i128,
+                };
+                value >= min && value <= max
+            }
+            (NumberValue::Float(value), _) if ty == ResolvedType::F32 => value <= f32::MAX as f64,
+            (NumberValue::Float(value), _) => value.is_finite(),
+            _ => true,
+        };
+
+        if !in_range {
+            self.errors.push(SemanticError::LiteralOutOfRange {
+                slice: slice.clone(),
+                ty: ty.clone(),
+            });
+        }
+
+        return ty;
+    }
+
+    fn invoke(&mut self, expr: &Expr, value: &Expr, params: &[Expr]) -> ResolvedType {
+        if let Some(func) = format_func(value, self.scope)
+            && self.local(&func.path[0]).is_none()
+        {
+            return self.format(expr, params);
+        }
+
+        let (callee, coalesce) = match &value.kind {
+            ExprKind::Field {
+                value: receiver,
+                access,
+                field,
+                generics: _,
+            } => {
+                let ty = self.expr(receiver, None);
+                self.field(value, &ty, *access, field, true)
+            }
+            _ => (self.expr(value, None), false),
+        };
+
+        let ResolvedType::Func { params: tys, ret } = callee else {
+            if !callee.is_opaque() {
+                self.errors.push(SemanticError::NotCallable {
+                    slice: value.slice.clone(),
+                    ty: callee,
+                });
+            }
+
+            for param in params {
+                self.expr(param, None);
+            }
+
+            return ResolvedType::Unknown;
+        };
+
+        if tys.len() != params.len() {
+            self.errors.push(SemanticError::ArgumentCount {
+                slice: expr.slice.clone(),
+                expected: tys.len(),
+                found: params.len(),
+            });
+        }
+
+        for (param, ty) in params.iter().zip(&tys) {
+            self.expect(param, ty);
+        }
+
+        for param in params.iter().skip(tys.len()) {
+            self.expr(param, None);
+        }
+
+        return match coalesce {
+            true => ResolvedType::Option(ret),
+            false => *ret,
+        };
+    }
+
+    /// Calls to formatting functions, the literal's placeholders decide what the other
+    /// arguments are
+    fn format(&mut self, expr: &Expr, params: &[Expr]) -> ResolvedType {
+        let Some((first, args)) = params.split_first() else {
+            self.errors.push(SemanticError::ArgumentCount {
+                slice: expr.slice.clone(),
+                expected: 1,
+                found: 0,
+            });
+            return ResolvedType::Unit;
+        };
+
+        let ExprKind::Primitive(PrimitiveExpr {
+            slice,
+            kind: PrimitiveExprKind::String(value),
+        }) = &first.kind
+        else {
+            // A single value is formatted on its own, like `Print(value)`
+            let ty = self.expr(first, None);
+
+            if !args.is_empty() {
+                self.errors.push(SemanticError::FormatNotLiteral {
+                    slice: first.slice.clone(),
+                });
+            } else if !self.formattable(&ty, FormatSpec::Display) {
+                self.errors.push(SemanticError::NotFormattable {
+                    slice: first.slice.clone(),
+                    spec: FormatSpec::Display,
+                    ty,
+                });
+            }
+
+            for arg in args {
+                self.expr(arg, None);
+            }
+            return ResolvedType::Unit;
+        };
+
+        let placeholders = match parse_format(value, slice) {
+            Ok(placeholders) => placeholders,
+            Err(slice) => {
+                self.errors.push(SemanticError::InvalidFormat { slice });
+
+                for arg in args {
+                    self.expr(arg, None);
+                }
+                return ResolvedType::Unit;
+            }
+        };
+
+        let positional = placeholders
+            .iter()
+            .filter(|it| it.arg == FormatArg::Next)
+            .count();
+
+        if positional != args.len() {
+            self.errors.push(SemanticError::FormatArgCount {
+                slice: slice.clone(),
+                expected: positional,
+                found: args.len(),
+            });
+        }
+
+        let mut args = args.iter();
+
+        for placeholder in &placeholders {
+            let ty = match &placeholder.arg {
+                FormatArg::Next => match args.next() {
+                    Some(arg) => self.expr(arg, None),
+                    None => continue,
+                },
+                FormatArg::Named(path) => {
+                    if self.local(&path.path[0]).is_none()
+                        && let Err(error) = self.scope.resolve(path)
+                    {
+                        self.errors.push(*error);
+                        continue;
+                    }
+                    self.variable(path)
+                }
+            };
+
+            if !self.formattable(&ty, placeholder.spec) {
+                self.errors.push(SemanticError::NotFormattable {
+                    slice: placeholder.slice.clone(),
+                    spec: placeholder.spec,
+                    ty,
+                });
+            }
+        }
+
+        for arg in args {
+            self.expr(arg, None);
+        }
+
+        return ResolvedType::Unit;
+    }
+
+    /// Primitives can be displayed, declared types have to implement `Std::Display`.
+    /// The number bases only work on integers
+    fn formattable(&self, ty: &ResolvedType, spec: FormatSpec) -> bool {
+        let ty = self.normalize(deref(ty));
+
+        if ty.is_opaque() {
+            return true;
+        }
+
+        return match (spec, &ty) {
+            (FormatSpec::Display, ResolvedType::Char | ResolvedType::Bool | ResolvedType::Str) => {
+                true
+            }
+            (FormatSpec::Display, ResolvedType::Declared { .. }) => {
+                self.implements(&ty, DISPLAY_TRAIT)
+            }
+            (FormatSpec::Display, _) => ty.is_numeric(),
+            _ => ty.is_integer(),
+        };
+    }
+
+    /// Whether `ty` has an impl of the trait at the full path `tr`
+    fn implements(&self, ty: &ResolvedType, tr: &[&str]) -> bool {
+        for (file, table) in self.tables.iter().enumerate() {
+            for im in &table.impls {
+                let generics = generic_names(&im.generics);
+
+                if let ResolvedType::Declared { path, .. } =
+                    self.resolve_in(file, &im.value.tr, &generics)
+                    && path.iter().map(|it| &**it).eq(tr.iter().copied())
+                    && self.resolve_in(file, &im.value.ty, &generics).same(ty)
+                {
+                    return true;
+                }
+            }
+        }
+
+        return false;
+    }
+
+    fn index(&mut self, value: &Expr, index: &Expr) -> ResolvedType {
+        let ty = self.expr(value, None);
+        let index_ty = self.expr(index, Some(&ResolvedType::Usize));
+
+        if !index_ty.is_integer() && !index_ty.is_opaque() {
+            self.errors.push(SemanticError::TypeMismatch {
+                slice: index.slice.clone(),
+                expected: ResolvedType::Usize,
+                found: index_ty,
+            });
+        }
+
+        let ty = self.normalize(deref(&ty));
+
+        return match ty {
+            ResolvedType::Array { ty, len: _ } | ResolvedType::Slice(ty) => *ty,
+            ResolvedType::Str => ResolvedType::U8,
+            _ if ty.is_opaque() => ResolvedType::Unknown,
+            _ => {
+                self.errors.push(SemanticError::NotIndexable {
+                    slice: value.slice.clone(),
+                    ty,
+                });
+                ResolvedType::Unknown
+            }
+        };
+    }
+
+    /// Returns the type of the field and whether the access wraps it in an option.
+    /// Methods are only considered when the field is invoked
+    fn field(
+        &mut self,
+        expr: &Expr,
+        ty: &ResolvedType,
+        access: AccessKind,
+        name: &Arc<str>,
+        invoked: bool,
+    ) -> (ResolvedType, bool) {
+        let ty = self.normalize(ty);
+        if ty.is_opaque() {
+            return (ResolvedType::Unknown, false);
+        }
+
+        let (base, coalesce) = match (access, &ty) {
+            (AccessKind::Value, ty) => (deref(ty), false),
+            (AccessKind::ValueCoalesce, ResolvedType::Option(ty)) => (deref(ty), true),
+            (AccessKind::ValueCascade, ResolvedType::Option(ty)) => (deref(ty), false),
+            (
+                AccessKind::Reference,
+                ResolvedType::Ref {
+                    ref_kind: RefKind::Pointer,
+                    ty,
+                },
+            ) => (&**ty, false),
+            (
+                AccessKind::ReferenceCoalesce,
+                ResolvedType::Ref {
+                    ref_kind: RefKind::Pointer,
+                    ty,
+                },
+            ) => (&**ty, true),
+            (
+                AccessKind::ReferenceCascade,
+                ResolvedType::Ref {
+                    ref_kind: RefKind::Pointer,
+                    ty,
+                },
+            ) => (&**ty, false),
+            _ => {
+                self.errors.push(SemanticError::InvalidAccess {
+                    slice: expr.slice.clone(),
+                    access,
+                    ty,
+                });
+                return (ResolvedType::Unknown, false);
+            }
+        };
+
+        let base = self.normalize(base);
+        if base.is_opaque() {
+            return (ResolvedType::Unknown, false);
+        }
+
+        let found = match self.struct_field(&base, name) {
+            Some(ty) => Some(ty),
+            None if invoked => self.method(&base, name),
+            None => None,
+        };
+
+        let Some(found) = found else {
+            self.errors.push(SemanticError::UnknownField {
+                slice: expr.slice.clone(),
+                ty: base,
+                field: name.clone(),
+            });
+            return (ResolvedType::Unknown, false);
+        };
+
+        return (found, coalesce);
+    }
+
+    fn struct_field(&self, ty: &ResolvedType, name: &str) -> Option<ResolvedType> {
+        return match ty {
+            ResolvedType::Array { .. } | ResolvedType::Slice(_) | ResolvedType::Str
+                if name == "Size" =>
+            {
+                Some(ResolvedType::Usize)
+            }
+            ResolvedType::Struct(fields) => fields
+                .iter()
+                .find(|it| &*it.name == name)
+                .map(|it| it.ty.clone()),
+            ResolvedType::Declared { path, generics } => {
+                let symbol = self.scope.tree.get(path)?;
+                let body = self.struct_body(symbol)?;
+                let param = body.params.iter().find(|it| &*it.name == name)?;
+
+                let names = generic_names(&symbol.symbol.generics);
+                let ty = self.resolve_in(symbol.file, &param.ty, &names);
+
+                Some(ty.substitute(&names, generics))
+            }
+            _ => None,
+        };
+    }
+
+    fn struct_body<'s>(&self, symbol: &'s GlobalSymbol) -> Option<&'s StructBody> {
+        return match &symbol.symbol.kind {
+            SymbolKind::Struct(st) => match &st.kind {
+                StructDeclKind::Value(body) => Some(body),
+                StructDeclKind::Wrapper(_) => None,
+            },
+            SymbolKind::Union(un) => Some(&un.body),
+            _ => None,
+        };
+    }
+
+    /// Finds a function declared for `ty` in an impl block, or in the trait it implements
+    fn method(&self, ty: &ResolvedType, name: &str) -> Option<ResolvedType> {
+        for (file, table) in self.tables.iter().enumerate() {
+            for im in &table.impls {
+                let generics = generic_names(&im.generics);
+
+                if !self.resolve_in(file, &im.value.ty, &generics).same(ty) {
+                    continue;
+                }
+
+                let mut decls: Vec<_> = im.value.body.decls.iter().map(|it| (file, it)).collect();
+
+                if let ResolvedType::Declared { path, .. } =
+                    self.resolve_in(file, &im.value.tr, &generics)
+                    && let Some(symbol) = self.scope.tree.get(&path)
+                    && let SymbolKind::Trait(tr) = &symbol.symbol.kind
+                {
+                    decls.extend(tr.body.decls.iter().map(|it| (symbol.file, it)));
+                }
+
+                for (file, decl) in decls {
+                    if let DeclLvl2Kind::Function(func) = &decl.value.kind
+                        && &*func.name == name
+                    {
+                        let mut generics = generics.clone();
+                        generics.extend(generic_names(&decl.generics));
+
+                        return Some(self.func_type(file, func, &generics));
+                    }
+                }
+            }
+        }
+
+        return None;
+    }
+
+    fn func_type(&self, file: usize, func: &FunctionDecl, generics: &[Arc<str>]) -> ResolvedType {
+        return ResolvedType::Func {
+            params: func
+                .params
+                .iter()
+                .map(|it| self.resolve_in(file, &it.ty, generics))
+                .collect(),
+            ret: Box::new(match &func.ret {
+                Some(ret) => self.resolve_in(file, ret, generics),
+                None => ResolvedType::Unit,
+            }),
+        };
+    }
+
+    fn bin_op(
+        &mut self,
+        expr: &Expr,
+        lhs: &Expr,
+        op: BinOp,
+        rhs: &Expr,
+        expected: Option<&ResolvedType>,
+    ) -> ResolvedType {
+        let comparison = matches!(
+            op,
+            BinOp::Equal
+                | BinOp::NotEqual
+                | BinOp::GreaterEqual
+                | BinOp::LessEqual
+                | BinOp::Greater
+                | BinOp::Less
+                | BinOp::BoolAnd
+                | BinOp::BoolOr
+                | BinOp::BoolXor
+        );
+
+        // Arithmetic keeps the type of its operands, so the expected type carries over to them
+        let operand = match (op, expected) {
+            (
+                BinOp::Range | BinOp::RangeFromTo | BinOp::RangeTo | BinOp::RangeFrom,
+                Some(ResolvedType::Range(ty)),
+            ) => Some(&**ty),
+            _ if comparison => None,
+            _ => expected,
+        };
+
+        // Number literals take the type of the other operand
+        let (l, r) = if is_number(lhs) && !is_number(rhs) {
+            let r = self.expr(rhs, operand);
+            let l = self.expr(lhs, Some(&r));
+            (l, r)
+        } else {
+            let l = self.expr(lhs, operand);
+            let r = self.expr(rhs, Some(&l));
+            (l, r)
+        };
+
+        return self.operands(expr, l, op, r);
+    }
+
+    /// The type of `l op r`, or the type it falls back to when the operands don't fit
+    fn operands(
+        &mut self,
+        expr: &Expr,
+        l: ResolvedType,
+        op: BinOp,
+        r: ResolvedType,
+    ) -> ResolvedType {
+        let comparison = matches!(
+            op,
+            BinOp::Equal
+                | BinOp::NotEqual
+                | BinOp::GreaterEqual
+                | BinOp::LessEqual
+                | BinOp::Greater
+                | BinOp::Less
+                | BinOp::BoolAnd
+                | BinOp::BoolOr
+                | BinOp::BoolXor
+        );
+
+        let fallback = match comparison {
+            true => ResolvedType::Bool,
+            false => ResolvedType::Unknown,
+        };
+
+        // Generics and declared types overload operators through traits
+        if l.is_opaque()
+            || r.is_opaque()
+            || matches!(l, ResolvedType::Declared { .. })
+            || matches!(r, ResolvedType::Declared { .. })
+        {
+            return fallback;
+        }
+
+        let result = match op {
+            BinOp::Mul | BinOp::Div | BinOp::Rem | BinOp::Add | BinOp::Sub
+                if l.is_numeric() && l == r =>
+            {
+                Some(l.clone())
+            }
+            BinOp::Shr | BinOp::Shl if l.is_integer() && r.is_integer() => Some(l.clone()),
+            BinOp::BitAnd | BinOp::BitOr | BinOp::BitXor
+                if (l.is_integer() || l == ResolvedType::Bool) && l == r =>
+            {
+                Some(l.clone())
+            }
+            BinOp::Equal | BinOp::NotEqual if l.same(&r) => Some(ResolvedType::Bool),
+            BinOp::GreaterEqual | BinOp::LessEqual | BinOp::Greater | BinOp::Less
+                if (l.is_numeric() || l == ResolvedType::Char) && l == r =>
+            {
+                Some(ResolvedType::Bool)
+            }
+            BinOp::BoolAnd | BinOp::BoolOr | BinOp::BoolXor
+                if l == ResolvedType::Bool && r == ResolvedType::Bool =>
+            {
+                Some(ResolvedType::Bool)
+            }
+            BinOp::Range | BinOp::RangeFromTo | BinOp::RangeTo | BinOp::RangeFrom
+                if (l.is_integer() || l == ResolvedType::Char) && l == r =>
+            {
+                Some(ResolvedType::Range(Box::new(l.clone())))
+            }
+            _ => None,
+        };
+
+        let Some(result) = result else {
+            self.errors.push(SemanticError::InvalidOperands {
+                slice: expr.slice.clone(),
+                op,
+                lhs: l,
+                rhs: r,
+            });
+            return fallback;
+        };
+
+        return result;
+    }
+
+    fn unary_op(
+        &mut self,
+        expr: &Expr,
+        op: UnaryOp,
+        value: &Expr,
+        expected: Option<&ResolvedType>,
+    ) -> ResolvedType {
+        // Negative literals are range checked as a whole
+        if let (
+            UnaryOp::Sub,
+            ExprKind::Primitive(PrimitiveExpr {
+                slice: _,
+                kind: PrimitiveExprKind::Number(number),
+            }),
+        ) = (op, &value.kind)
+        {
+            return self.number(&expr.slice, number, true, expected);
+        }
+
+        let operand = match op {
+            UnaryOp::Add | UnaryOp::Sub | UnaryOp::BitNot => expected,
+            _ => None,
+        };
+
+        if let UnaryOp::Increment
+        | UnaryOp::Decrement
+        | UnaryOp::PostIncrement
+        | UnaryOp::PostDecrement = op
+        {
+            self.assignable(value);
+        }
+
+        let ty = self.expr(value, operand);
+
+        let result = match (op, &ty) {
+            (UnaryOp::Reference, _) => Some(ResolvedType::Ref {
+                ref_kind: match self.is_mut(value) {
+                    true => RefKind::Mutable,
+                    false => RefKind::Immutable,
+                },
+                ty: Box::new(ty.clone()),
+            }),
+            (UnaryOp::Pointer, _) => Some(ResolvedType::Ref {
+                ref_kind: RefKind::Pointer,
+                ty: Box::new(ty.clone()),
+            }),
+            // Generics and declared types overload operators through traits
+            (_, ResolvedType::Declared { .. }) => Some(ResolvedType::Unknown),
+            (_, ty) if ty.is_opaque() => Some(ResolvedType::Unknown),
+            (UnaryOp::Add | UnaryOp::Sub, ty) if ty.is_numeric() => Some(ty.clone()),
+            (UnaryOp::BoolNot, ResolvedType::Bool) => Some(ResolvedType::Bool),
+            (UnaryOp::BitNot, ty) if ty.is_integer() => Some(ty.clone()),
+            (
+                UnaryOp::Increment
+                | UnaryOp::Decrement
+                | UnaryOp::PostIncrement
+                | UnaryOp::PostDecrement,
+                ty,
+            ) if ty.is_numeric() => Some(ty.clone()),
+            (UnaryOp::Deref, ResolvedType::Ref { ref_kind: _, ty }) => Some(*ty.clone()),
+            (UnaryOp::Coalesce, ResolvedType::Option(_)) => Some(ty.clone()),
+            (
+                UnaryOp::Coalesce,
+                ResolvedType::Ref {
+                    ref_kind: RefKind::Pointer,
+                    ty,
+                },
+            ) => Some(ResolvedType::Option(Box::new(ResolvedType::Ref {
+                ref_kind: RefKind::Immutable,
+                ty: ty.clone(),
+            }))),
+            (UnaryOp::Cascade, ResolvedType::Option(ty)) => Some(*ty.clone()),
+            (
+                UnaryOp::Cascade,
+                ResolvedType::Ref {
+                    ref_kind: RefKind::Pointer,
+                    ty,
+                },
+            ) => Some(ResolvedType::Ref {
+                ref_kind: RefKind::Immutable,
+                ty: ty.clone(),
+            }),
+            _ => None,
+        };
+
+        let Some(result) = result else {
+            self.errors.push(SemanticError::InvalidOperand {
+                slice: expr.slice.clone(),
+                op,
+                ty,
+            });
+            return ResolvedType::Unknown;
+        };
+
+        return result;
+    }
+
+    fn assign(&mut self, expr: &Expr, target: &Expr, op: AssignOp, value: &Expr) -> ResolvedType {
+        self.assignable(target);
+
+        let ty = self.expr(target, None);
+
+        match op.bin_op() {
+            None => self.expect(value, &ty),
+            Some((bin_op, complement)) => {
+                let value = match complement {
+                    true => self.unary_op(expr, UnaryOp::BitNot, value, Some(&ty)),
+                    false => self.expr(value, Some(&ty)),
+                };
+                let result = self.operands(expr, ty.clone(), bin_op, value);
+
+                if !ty.accepts(&result) {
+                    self.errors.push(SemanticError::TypeMismatch {
+                        slice: expr.slice.clone(),
+                        expected: ty,
+                        found: result,
+                    });
+                }
+            }
+        }
+
+        return ResolvedType::Unit;
+    }
+
+    /// Reports assignments to things that aren't places, or that can't be changed
+    fn assignable(&mut self, target: &Expr) {
+        let place = matches!(
+            target.kind,
+            ExprKind::Variable { .. }
+                | ExprKind::Field { .. }
+                | ExprKind::Index { .. }
+                | ExprKind::This
+                | ExprKind::UnaryOp {
+                    op: UnaryOp::Deref,
+                    ..
+                }
+        );
+
+        if !place {
+            self.errors.push(SemanticError::InvalidAssignment {
+                slice: target.slice.clone(),
+            });
+        } else if !self.is_mut(target) {
+            self.errors.push(SemanticError::ImmutableAssignment {
+                slice: target.slice.clone(),
+            });
+        }
+    }
+
+    fn is_mut(&self, expr: &Expr) -> bool {
+        return match &expr.kind {
+            ExprKind::Variable { path, .. } if path.path.len() == 1 => {
+                match self.local(&path.path[0]) {
+                    Some(local) => local.is_mut,
+                    None => self.is_mut_global(path),
+                }
+            }
+            ExprKind::Variable { path, .. } => self.is_mut_global(path),
+            // Pointers can always be written through
+            ExprKind::Field {
+                access: AccessKind::Reference,
+                ..
+            } => true,
+            ExprKind::Field { value, .. } | ExprKind::Index { value, .. } => {
+                self.is_mut(value)
+                    || matches!(
+                        self.ref_kind(value),
+                        Some(RefKind::Mutable | RefKind::Pointer)
+                    )
+            }
+            ExprKind::UnaryOp {
+                op: UnaryOp::Deref,
+                value,
+            } => self.ref_kind(value) != Some(RefKind::Immutable),
+            ExprKind::This => self.this_value.as_ref().is_some_and(|it| it.1),
+            _ => false,
+        };
+    }
+
+    fn is_mut_global(&self, path: &IdentPath) -> bool {
+        return match self.scope.resolve(path) {
+            Ok(resolved) => match &resolved.symbol.symbol.kind {
+                SymbolKind::Variable(var) => var.modifier == VariableModifier::Mut,
+                _ => false,
+            },
+            // Already reported
+            Err(_) => true,
+        };
+    }
+
+    /// How a local refers to its value, if it's a reference
+    fn ref_kind(&self, expr: &Expr) -> Option<RefKind> {
+        let ExprKind::Variable { path, .. } = &expr.kind else {
+            return None;
+        };
+
+        let local = self.local(path.path.first()?)?;
+        return match &local.ty {
+            ResolvedType::Ref { ref_kind, .. } => Some(*ref_kind),
+            _ => None,
+        };
+    }
+
+    fn castable(&self, from: &ResolvedType, to: &ResolvedType) -> bool {
+        if from.is_opaque() || to.is_opaque() || to.accepts(from) {
+            return true;
+        }
+
+        if let Some(inner) = self.wrapped(from)
+            && to.accepts(&inner)
+        {
+            return true;
+        }
+
+        if let Some(inner) = self.wrapped(to)
+            && inner.accepts(from)
+        {
+            return true;
+        }
+
+        return match (from, to) {
+            (from, to) if from.is_numeric() && to.is_numeric() => true,
+            (ResolvedType::Char | ResolvedType::Bool, to) if to.is_integer() => true,
+            (ResolvedType::U8 | ResolvedType::U32, ResolvedType::Char) => true,
+            (
+                ResolvedType::Ref { .. },
+                ResolvedType::Ref {
+                    ref_kind: RefKind::Pointer,
+                    ..
+                },
+            ) => true,
+            (
+                ResolvedType::Ref {
+                    ref_kind: RefKind::Pointer,
+                    ..
+                },
+                ResolvedType::Usize | ResolvedType::Isize,
+            )
+            | (
+                ResolvedType::Usize | ResolvedType::Isize,
+                ResolvedType::Ref {
+                    ref_kind: RefKind::Pointer,
+                    ..
+                },
+            ) => true,
+            (ResolvedType::Declared { path, .. }, to) if to.is_integer() => self
+                .scope
+                .tree
+                .get(path)
+                .is_some_and(|it| match &it.symbol.kind {
+                    SymbolKind::Enum(en) => matches!(en.kind, EnumDeclKind::Int { .. }),
+                    _ => false,
+                }),
+            _ => false,
+        };
+    }
+
+    /// The inner type of a wrapper struct
+    fn wrapped(&self, ty: &ResolvedType) -> Option<ResolvedType> {
+        let ResolvedType::Declared { path, generics } = ty else {
+            return None;
+        };
+
+        let symbol = self.scope.tree.get(path)?;
+        let SymbolKind::Struct(st) = &symbol.symbol.kind else {
+            return None;
+        };
+        let StructDeclKind::Wrapper(inner) = &st.kind else {
+            return None;
+        };
+
+        let names = generic_names(&symbol.symbol.generics);
+        return Some(
+            self.resolve_in(symbol.file, inner, &names)
+                .substitute(&names, generics),
+        );
+    }
+
+    fn variable(&mut self, path: &IdentPath) -> ResolvedType {
+        if path.path.len() == 1
+            && let Some(local) = self.local(&path.path[0])
+        {
+            return local.ty.clone();
+        }
+
+        if self.generics.contains(&path.path[0]) {
+            return ResolvedType::Unknown;
+        }
+
+        // Unresolved paths were already reported by resolve_paths
+        let Ok(resolved) = self.scope.resolve(path) else {
+            return ResolvedType::Unknown;
+        };
+
+        let symbol = resolved.symbol;
+        let names = generic_names(&symbol.symbol.generics);
+
+        return match (&resolved.member, &symbol.symbol.kind) {
+            (None, SymbolKind::Function(func)) => self.func_type(symbol.file, func, &names),
+            (None, SymbolKind::Variable(var)) => match &var.ty {
+                Some(ty) => self.resolve_in(symbol.file, ty, &names),
+                None => ResolvedType::Unknown,
+            },
+            (Some(member), _) => {
+                let ty = ResolvedType::Declared {
+                    path: symbol.symbol.path.clone(),
+                    generics: names.iter().map(|_| ResolvedType::Unknown).collect(),
+                };
+
+                match (self.method(&ty, member), &symbol.symbol.kind) {
+                    (Some(func), _) => func,
+                    (None, SymbolKind::Enum(_)) => ty,
+                    (None, _) => ResolvedType::Unknown,
+                }
+            }
+            _ => ResolvedType::Unknown,
+        };
+    }
+
+    fn initializer(
+        &mut self,
+        path: &IdentPath,
+        generics: &Option<GenericsInstance>,
+        list: &InitializerList,
+    ) -> ResolvedType {
+        let args: Vec<_> = match generics {
+            Some(generics) => generics.params.iter().map(|it| self.resolve(it)).collect(),
+            None => vec![],
+        };
+
+        let resolved = match self.scope.resolve(path) {
+            Ok(resolved) if !self.generics.contains(&path.path[0]) => resolved,
+            _ => {
+                self.initializer_list(list);
+                return ResolvedType::Unknown;
+            }
+        };
+
+        let symbol = resolved.symbol;
+        let names = generic_names(&symbol.symbol.generics);
+
+        let ty = ResolvedType::Declared {
+            path: symbol.symbol.path.clone(),
+            generics: match args.is_empty() {
+                true => names.iter().map(|_| ResolvedType::Unknown).collect(),
+                false => args,
+            },
+        };
+
+        if resolved.member.is_some() {
+            self.initializer_list(list);
+            return ty;
+        }
+
+        if let Some(inner) = self.wrapped(&ty) {
+            match &list.kind {
+                InitializerKind::Expr(values) if values.len() == 1 => {
+                    self.expect(&values[0], &inner);
+                }
+                _ => self.initializer_list(list),
+            }
+            return ty;
+        }
+
+        let Some(body) = self.struct_body(symbol) else {
+            self.initializer_list(list);
+            return ty;
+        };
+
+        match &list.kind {
+            InitializerKind::Expr(values) => {
+                if values.len() != body.params.len() {
+                    self.errors.push(SemanticError::ArgumentCount {
+                        slice: list.slice.clone(),
+                        expected: body.params.len(),
+                        found: values.len(),
+                    });
+                }
+
+                for (value, param) in values.iter().zip(&body.params) {
+                    let field = self.struct_field(&ty, &param.name).unwrap();
+                    self.expect(value, &field);
+                }
+
+                for value in values.iter().skip(body.params.len()) {
+                    self.expr(value, None);
+                }
+            }
+            InitializerKind::Named { values, default } => {
+                for value in values {
+                    match self.struct_field(&ty, &value.name) {
+                        Some(field) => self.expect(&value.value, &field),
+                        None => {
+                            self.errors.push(SemanticError::UnknownField {
+                                slice: value.slice.clone(),
+                                ty: ty.clone(),
+                                field: value.name.clone(),
+                            });
+                            self.expr(&value.value, None);
+                        }
+                    }
+                }
+
+                if let Some(default) = default {
+                    self.expr(&default.value, None);
+                }
+            }
+            InitializerKind::Empty => {}
+        }
+
+        return ty;
+    }
+
+    /// Infers the values of an initializer that can't be checked against a type
+    fn initializer_list(&mut self, list: &InitializerList) {
+        match &list.kind {
+            InitializerKind::Expr(values) => {
+                for value in values {
+                    self.expr(value, None);
+                }
+            }
+            InitializerKind::Named { values, default } => {
+                for value in values {
+                    self.expr(&value.value, None);
+                }
+                if let Some(default) = default {
+                    self.expr(&default.value, None);
+                }
+            }
+            InitializerKind::Empty => {}
+        }
+    }
+
+    fn local(&self, name: &Arc<str>) -> Option<&Local> {
+        return self.locals.iter().rev().find(|it| it.name == *name);
+    }
+
+    /// Replaces `This` with the type of the enclosing impl block
+    fn normalize(&self, ty: &ResolvedType) -> ResolvedType {
+        return match (ty, &self.this) {
+            (ResolvedType::This, Some(this)) => this.clone(),
+            _ => ty.clone(),
+        };
+    }
+
+    fn resolve(&self, ty: &Type) -> ResolvedType {
+        return self.resolve_in(self.scope.file, ty, &self.generics);
+    }
+
+    /// Resolves a type written in another file, errors were already reported by resolve_paths
+    fn resolve_in(&self, file: usize, ty: &Type, generics: &[Arc<str>]) -> ResolvedType {
+        return self.scopes[file].resolve_type(ty, generics, &mut vec![]);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        parse_tree::{expr::BinOp, parse::error::ParserError},
+        semantic_model::{
+            error::SemanticError, fixture::analyze, format::FormatSpec, types::ResolvedType,
+        },
+    };
+
+    use super::check_types;
+
+    fn check(src: &str) -> Result<Vec<SemanticError>, ParserError> {
+        return analyze(&[src], check_types);
+    }
+
+    #[test]
+    fn well_typed() -> Result<(), ParserError> {
+        let errors = check(
+            "struct Point { x: i32, y: i32 }
+            trait Show { func Len(ref this): i32; }
+            impl Show for Point { func Len(ref this): i32 => this.x + this.y; }
+            func Add(a: i32, b: i32): i32 => a + b;
+            func Main(points: ref [Point], next: *Point): bool {
+                let p = Point { .x = 1, .y = Add(2, 3) };
+                let len = p.Len();
+                let first = points[0].x + next->y + len;
+                let ratio: f32 = first:f32 / 2.5;
+                return ratio > 1 && points.Size > 0;
+            }",
+        )?;
+
+        assert_eq!(errors, vec![]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn mismatch() -> Result<(), ParserError> {
+        let errors = check("func Main() { let x: i32 = \"hi\"; }")?;
+
+        let [SemanticError::TypeMismatch {
+            slice,
+            expected: ResolvedType::I32,
+            found: ResolvedType::Ref { .. },
+        }] = &errors[..]
+        else {
+            panic!("expected a single type mismatch, got {errors:?}");
+        };
+        assert_eq!(&*slice.value(), "\"hi\"");
+
+        Ok(())
+    }
+
+    #[test]
+    fn ill_typed() -> Result<(), ParserError> {
+        let errors = check(
+            "struct Point { x: i32 }
+            func Add(a: i32, b: i32): i32 => a + b;
+            func Main(p: Point, opt: ?Point) {
+                let a = Add(1);
+                let b = p.z;
+                let c = p->x;
+                let d = opt?.x;
+                let e = true + 1;
+                let f = p[0];
+                let g = p:i32;
+            }",
+        )?;
+
+        let [SemanticError::ArgumentCount {
+            expected: 2,
+            found: 1,
+            ..
+        }, SemanticError::UnknownField { field, .. }, SemanticError::InvalidAccess { .. }, SemanticError::InvalidOperands {
+            op: BinOp::Add,
+            lhs: ResolvedType::Bool,
+            rhs: ResolvedType::I32,
+            ..
+        }, SemanticError::NotIndexable { .. }, SemanticError::InvalidCast { .. }] = &errors[..]
+        else {
+            panic!("expected one error per bad expression, got {errors:?}");
+        };
+        assert_eq!(&**field, "z");
+
+        Ok(())
+    }
+
+    #[test]
+    fn literals() -> Result<(), ParserError> {
+        let errors = check(
+            "func Main(big: u64): f32 {
+                let a: u8 = 255;
+                let b: i8 = -128;
+                let c = big + 18446744073709551615;
+                let d: ?u16 = 65535;
+                let e: u8 = 300;
+                let f: i8 = -129;
+                let g: i32 = 2.5;
+                let h: u32 = 1.0;
+                let i = 2147483648;
+                let j: u64 = 5u8;
+                let k = 300u8;
+                let l: u8 = 2u8 + 3;
+                let m: f32 = 1e39;
+                let n = 1e309;
+                return 1 + 2.5;
+            }",
+        )?;
+
+        let [SemanticError::LiteralOutOfRange {
+            slice: e,
+            ty: ResolvedType::U8,
+        }, SemanticError::LiteralOutOfRange {
+            slice: f,
+            ty: ResolvedType::I8,
+        }, SemanticError::TypeMismatch {
+            slice: g,
+            expected: ResolvedType::I32,
+            found: ResolvedType::F64,
+        }, SemanticError::TypeMismatch {
+            slice: h,
+            expected: ResolvedType::U32,
+            found: ResolvedType::F64,
+        }, SemanticError::LiteralOutOfRange {
+            slice: i,
+            ty: ResolvedType::I32,
+        }, SemanticError::TypeMismatch {
+            slice: j,
+            expected: ResolvedType::U64,
+            found: ResolvedType::U8,
+        }, SemanticError::LiteralOutOfRange {
+            slice: k,
+            ty: ResolvedType::U8,
+        }, SemanticError::LiteralOutOfRange {
+            slice: m,
+            ty: ResolvedType::F32,
+        }, SemanticError::LiteralOutOfRange {
+            slice: n,
+            ty: ResolvedType::F64,
+        }] = &errors[..]
+        else {
+            panic!("expected one error per bad literal, got {errors:?}");
+        };
+
+        assert_eq!(&*e.value(), "300");
+        assert_eq!(&*f.value(), "-129");
+        assert_eq!(&*g.value(), "2.5");
+        assert_eq!(&*h.value(), "1.0");
+        assert_eq!(&*i.value(), "2147483648");
+        assert_eq!(&*j.value(), "5u8");
+        assert_eq!(&*k.value(), "300u8");
+        assert_eq!(&*m.value(), "1e39");
+        assert_eq!(&*n.value(), "1e309");
+
+        Ok(())
+    }
+
+    #[test]
+    fn byte_strings() -> Result<(), ParserError> {
+        let errors = check(
+            r#"func Main() {
+                let a: [u8, 3] = b"abc";
+                let b: [u8, 4] = c"abc";
+                let c: [u8, 3] = c"abc";
+            }"#,
+        )?;
+
+        let [SemanticError::TypeMismatch {
+            slice,
+            expected: ResolvedType::Array { len: 3, .. },
+            found: ResolvedType::Array { len: 4, .. },
+        }] = &errors[..]
+        else {
+            panic!("expected a single type mismatch, got {errors:?}");
+        };
+        assert_eq!(&*slice.value(), r#"c"abc""#);
+
+        Ok(())
+    }
+
+    #[test]
+    fn loops() -> Result<(), ParserError> {
+        let errors = check(
+            r#"func Main(n: i32, s: str) {
+                for (let i = 0, i < n, i + 1) {
+                    let a: i32 = i;
+                }
+                for (x in 0..n) {
+                    let b: i32 = x;
+                    let c: bool = x;
+                }
+                for (y in s) {}
+                while (n) {}
+                loop {
+                    let d: bool = n > 0;
+                    break if d;
+                    break if n;
+                }
+            }"#,
+        )?;
+
+        let [SemanticError::TypeMismatch {
+            expected: ResolvedType::Bool,
+            found: ResolvedType::I32,
+            ..
+        }, SemanticError::NotIterable { slice, .. }, SemanticError::TypeMismatch {
+            expected: ResolvedType::Bool,
+            found: ResolvedType::I32,
+            slice: condition,
+        }, SemanticError::TypeMismatch {
+            expected: ResolvedType::Bool,
+            slice: break_condition,
+            ..
+        }] = &errors[..]
+        else {
+            panic!("expected four errors, got {errors:?}");
+        };
+        assert_eq!(&*slice.value(), "s");
+        assert_eq!(&*condition.value(), "n");
+        assert_eq!(&*break_condition.value(), "n");
+
+        Ok(())
+    }
+
+    #[test]
+    fn assignments() -> Result<(), ParserError> {
+        let errors = check(
+            r#"struct Point { x: i32 }
+            mut total: i32 = 0;
+            let limit: i32 = 10;
+            func Get(): i32 => 1;
+            func Main(p: Point, r: ref mut Point, s: ref Point, arr: ref mut [i32], ptr: *Point) {
+                mut q = p;
+                mut n = 1;
+                q.x = 2;
+                r.x += 1;
+                arr[0] = arr[1];
+                ptr->x = 3;
+                n <<= 2;
+                n ~= 4;
+                n++;
+                --n;
+                total = n;
+                p.x = 1;
+                s.x = 1;
+                limit = 1;
+                n = true;
+                n += 1.5;
+                mut flag = true;
+                flag &= false;
+                flag ~= false;
+                1 = n;
+                Get()++;
+            }"#,
+        )?;
+
+        let found: Vec<_> = errors
+            .iter()
+            .map(|it| match it {
+                SemanticError::ImmutableAssignment { slice } => {
+                    format!("immutable {}", slice.value())
+                }
+                SemanticError::InvalidAssignment { slice } => format!("invalid {}", slice.value()),
+                SemanticError::TypeMismatch { slice, .. } => format!("mismatch {}", slice.value()),
+                SemanticError::InvalidOperands { slice, .. } => {
+                    format!("operands {}", slice.value())
+                }
+                SemanticError::InvalidOperand { slice, .. } => format!("operand {}", slice.value()),
+                _ => format!("{it:?}"),
+            })
+            .collect();
+
+        assert_eq!(
+            found,
+            [
+                "immutable p.x",
+                "immutable s.x",
+                "immutable limit",
+                "mismatch true",
+                "operands n += 1.5",
+                "operand flag ~= false",
+                "invalid 1",
+                "invalid Get()",
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn arrays() -> Result<(), ParserError> {
+        let errors = check(
+            r#"func Main() {
+                let a: [i32, 3] = [1, 2, 3];
+                let b: [u8, 4] = [0; 4];
+                let grid: [[i32, 2], 2] = [[1, 2], [3, 4]];
+                let c = [1, 2];
+                let d: [i32, 2] = c;
+                let e: [i32, 3] = [1, 2];
+                let f: [i32, 2] = [0; 3];
+                let g: [bool, 2] = [true, 1];
+                let h = [1, true];
+                let i: [[i32, 2], 1] = [[1, 2, 3]];
+            }"#,
+        )?;
+
+        let found: Vec<_> = errors
+            .iter()
+            .map(|it| match it {
+                SemanticError::ArrayLength {
+                    slice,
+                    expected,
+                    found,
+                } => format!("length {} {expected} {found}", slice.value()),
+                SemanticError::TypeMismatch { slice, .. } => format!("mismatch {}", slice.value()),
+                _ => format!("{it:?}"),
+            })
+            .collect();
+
+        assert_eq!(
+            found,
+            [
+                "length [1, 2] 3 2",
+                "length [0; 3] 2 3",
+                "mismatch 1",
+                "mismatch true",
+                "length [1, 2, 3] 2 3",
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn format_strings() -> Result<(), ParserError> {
+        let std = "namespace Std; pub trait Display { func Width(ref this): usize; }";
+        let src = r#"struct Point { x: i32 }
+            struct Meters { value: f64 }
+            impl Std::Display for Meters { func Width(ref this): usize => 1; }
+            trait Display { func Width(ref this): usize; }
+            impl Display for Point { func Width(ref this): usize => 1; }
+            func Main(p: Point, m: Meters, n: i32, f: f64) {
+                Print("{} and {n:x} at {}", m, "here");
+                Print(n);
+                PrintLn("{}\u{7B}\u{7B} {{literal}}", f);
+                Print(r"{}", p);
+                Print("{:x}", f);
+                Print("{} {}", n);
+                Print("{missing}");
+                Print("{:q}", n);
+                Print("\x7B{}\x7D", n);
+                Print("\t{:q}", n);
+                Print(n, n);
+            }"#;
+        let errors = analyze(&[std, src], check_types)?;
+
+        let [SemanticError::NotFormattable {
+            slice: p,
+            spec: FormatSpec::Display,
+            ty: ResolvedType::Declared { .. },
+        }, SemanticError::NotFormattable {
+            slice: f,
+            spec: FormatSpec::LowerHex,
+            ty: ResolvedType::F64,
+        }, SemanticError::FormatArgCount {
+            slice: count,
+            expected: 2,
+            found: 1,
+        }, SemanticError::UnknownPath { path: missing }, SemanticError::InvalidFormat { slice: spec }, SemanticError::FormatArgCount {
+            slice: escaped,
+            expected: 0,
+            found: 1,
+        }, SemanticError::InvalidFormat { slice: tab }, SemanticError::FormatNotLiteral { slice: n }] =
+            &errors[..]
+        else {
+            panic!("expected one error per bad format, got {errors:?}");
+        };
+
+        assert_eq!(&*p.value(), "{}");
+        assert_eq!(p.start, p.src.find(r#"r"{}""#).unwrap() + 2);
+        assert_eq!(&*f.value(), "{:x}");
+        assert_eq!(&*count.value(), r#""{} {}""#);
+        assert_eq!(&*missing.slice.value(), "missing");
+        assert_eq!(&*spec.value(), "{:q}");
+        // Escaped braces are braces, and after an escape the whole literal is pointed at
+        assert_eq!(&*escaped.value(), r#""\x7B{}\x7D""#);
+        assert_eq!(&*tab.value(), r#""\t{:q}""#);
+        assert_eq!(&*n.value(), "n");
+
+        Ok(())
+    }
+}
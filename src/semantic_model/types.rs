@@ -0,0 +1,513 @@
+use std::{fmt::Display, sync::Arc};
+
+use crate::{
+    parse_tree::types::{RefKind, Type, TypeKind},
+    tokenizer::token::OperatorTrait,
+};
+
+use super::{error::SemanticError, namespace::Scope, symbol::SymbolKind};
+
+/// A type with every path resolved to the symbol it names
+#[derive(Debug, Clone, PartialEq)]
+pub enum ResolvedType {
+    Char,
+    Bool,
+
+    U8,
+    I8,
+    U16,
+    I16,
+    U32,
+    I32,
+    U64,
+    I64,
+    Usize,
+    Isize,
+
+    F32,
+    F64,
+
+    Unit,
+    Never,
+
+    This,
+
+    Str,
+
+    Ref {
+        ref_kind: RefKind,
+        ty: Box<ResolvedType>,
+    },
+
+    Array {
+        ty: Box<ResolvedType>,
+        len: usize,
+    },
+    Slice(Box<ResolvedType>),
+
+    Option(Box<ResolvedType>),
+    Range(Box<ResolvedType>),
+
+    Func {
+        params: Vec<ResolvedType>,
+        ret: Box<ResolvedType>,
+    },
+
+    Struct(Vec<ResolvedField>),
+
+    Declared {
+        path: Vec<Arc<str>>,
+        generics: Vec<ResolvedType>,
+    },
+    /// A built-in operator trait, which doesn't need to be declared
+    Operator {
+        op: OperatorTrait,
+        generics: Vec<ResolvedType>,
+    },
+    Generic(Arc<str>),
+
+    /// A type that failed to resolve, the error has already been reported
+    Unknown,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResolvedField {
+    pub name: Arc<str>,
+    pub ty: ResolvedType,
+}
+
+impl ResolvedType {
+    pub fn is_integer(&self) -> bool {
+        return matches!(
+            self,
+            Self::U8
+                | Self::I8
+                | Self::U16
+                | Self::I16
+                | Self::U32
+                | Self::I32
+                | Self::U64
+                | Self::I64
+                | Self::Usize
+                | Self::Isize
+        );
+    }
+
+    /// The inclusive range of values an integer type can hold
+    pub fn int_range(&self) -> Option<(i128, i128)> {
+        let range = match self {
+            Self::U8 => (u8::MIN as i128, u8::MAX as i128),
+            Self::I8 => (i8::MIN as i128, i8::MAX as i128),
+            Self::U16 => (u16::MIN as i128, u16::MAX as i128),
+            Self::I16 => (i16::MIN as i128, i16::MAX as i128),
+            Self::U32 => (u32::MIN as i128, u32::MAX as i128),
+            Self::I32 => (i32::MIN as i128, i32::MAX as i128),
+            Self::U64 | Self::Usize => (u64::MIN as i128, u64::MAX as i128),
+            Self::I64 | Self::Isize => (i64::MIN as i128, i64::MAX as i128),
+            _ => return None,
+        };
+
+        return Some(range);
+    }
+
+    pub fn is_float(&self) -> bool {
+        return matches!(self, Self::F32 | Self::F64);
+    }
+
+    pub fn is_numeric(&self) -> bool {
+        return self.is_integer() || self.is_float();
+    }
+
+    /// Types that can't be checked until generics are instantiated, or that already failed
+    pub fn is_opaque(&self) -> bool {
+        return matches!(self, Self::Generic(_) | Self::This | Self::Unknown);
+    }
+
+    /// Structural equality, where opaque types are equal to anything
+    pub fn same(&self, other: &Self) -> bool {
+        if self.is_opaque() || other.is_opaque() {
+            return true;
+        }
+
+        return match (self, other) {
+            (
+                Self::Ref {
+                    ref_kind: a,
+                    ty: ty_a,
+                },
+                Self::Ref {
+                    ref_kind: b,
+                    ty: ty_b,
+                },
+            ) => a == b && ty_a.same(ty_b),
+            (Self::Array { ty: a, len: len_a }, Self::Array { ty: b, len: len_b }) => {
+                len_a == len_b && a.same(b)
+            }
+            (Self::Slice(a), Self::Slice(b))
+            | (Self::Option(a), Self::Option(b))
+            | (Self::Range(a), Self::Range(b)) => a.same(b),
+            (
+                Self::Func {
+                    params: params_a,
+                    ret: ret_a,
+                },
+                Self::Func {
+                    params: params_b,
+                    ret: ret_b,
+                },
+            ) => {
+                params_a.len() == params_b.len()
+                    && params_a.iter().zip(params_b).all(|(a, b)| a.same(b))
+                    && ret_a.same(ret_b)
+            }
+            (Self::Struct(a), Self::Struct(b)) => {
+                a.len() == b.len()
+                    && a.iter()
+                        .zip(b)
+                        .all(|(a, b)| a.name == b.name && a.ty.same(&b.ty))
+            }
+            (
+                Self::Declared {
+                    path: a,
+                    generics: generics_a,
+                },
+                Self::Declared {
+                    path: b,
+                    generics: generics_b,
+                },
+            ) => {
+                a == b
+                    && generics_a.len() == generics_b.len()
+                    && generics_a.iter().zip(generics_b).all(|(a, b)| a.same(b))
+            }
+            (
+                Self::Operator {
+                    op: a,
+                    generics: generics_a,
+                },
+                Self::Operator {
+                    op: b,
+                    generics: generics_b,
+                },
+            ) => {
+                a == b
+                    && generics_a.len() == generics_b.len()
+                    && generics_a.iter().zip(generics_b).all(|(a, b)| a.same(b))
+            }
+            _ => self == other,
+        };
+    }
+
+    /// Whether a value of type `other` can be used where `self` is expected
+    pub fn accepts(&self, other: &Self) -> bool {
+        if let Self::Never = other {
+            return true;
+        }
+
+        return match (self, other) {
+            (
+                Self::Ref {
+                    ref_kind: a,
+                    ty: ty_a,
+                },
+                Self::Ref {
+                    ref_kind: b,
+                    ty: ty_b,
+                },
+            ) => {
+                // References downcast to immutable references and pointers, never the other way
+                let kind = a == b
+                    || matches!(
+                        (a, b),
+                        (RefKind::Immutable, RefKind::Mutable)
+                            | (RefKind::Pointer, RefKind::Mutable | RefKind::Immutable)
+                    );
+
+                if let (Self::Slice(a), Self::Array { ty: b, len: _ }) = (&**ty_a, &**ty_b) {
+                    return kind && a.same(b);
+                }
+
+                kind && ty_a.same(ty_b)
+            }
+            (Self::Option(a), Self::Option(b)) => a.same(b),
+            (Self::Option(a), b) => a.accepts(b),
+            _ => self.same(other),
+        };
+    }
+
+    /// Replaces the generic types named in `names` with the matching type in `args`
+    pub fn substitute(&self, names: &[Arc<str>], args: &[ResolvedType]) -> Self {
+        let sub = |ty: &ResolvedType| Box::new(ty.substitute(names, args));
+
+        return match self {
+            Self::Generic(name) => match names.iter().position(|it| it == name) {
+                Some(idx) if idx < args.len() => args[idx].clone(),
+                _ => self.clone(),
+            },
+            Self::Ref { ref_kind, ty } => Self::Ref {
+                ref_kind: *ref_kind,
+                ty: sub(ty),
+            },
+            Self::Array { ty, len } => Self::Array {
+                ty: sub(ty),
+                len: *len,
+            },
+            Self::Slice(ty) => Self::Slice(sub(ty)),
+            Self::Option(ty) => Self::Option(sub(ty)),
+            Self::Range(ty) => Self::Range(sub(ty)),
+            Self::Func { params, ret } => Self::Func {
+                params: params.iter().map(|it| it.substitute(names, args)).collect(),
+                ret: sub(ret),
+            },
+            Self::Struct(fields) => Self::Struct(
+                fields
+                    .iter()
+                    .map(|it| ResolvedField {
+                        name: it.name.clone(),
+                        ty: it.ty.substitute(names, args),
+                    })
+                    .collect(),
+            ),
+            Self::Declared { path, generics } => Self::Declared {
+                path: path.clone(),
+                generics: generics
+                    .iter()
+                    .map(|it| it.substitute(names, args))
+                    .collect(),
+            },
+            _ => self.clone(),
+        };
+    }
+}
+
+/// Written the way the type would be in source
+impl Display for ResolvedType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return match self {
+            Self::Char => write!(f, "char"),
+            Self::Bool => write!(f, "bool"),
+            Self::U8 => write!(f, "u8"),
+            Self::I8 => write!(f, "i8"),
+            Self::U16 => write!(f, "u16"),
+            Self::I16 => write!(f, "i16"),
+            Self::U32 => write!(f, "u32"),
+            Self::I32 => write!(f, "i32"),
+            Self::U64 => write!(f, "u64"),
+            Self::I64 => write!(f, "i64"),
+            Self::Usize => write!(f, "usize"),
+            Self::Isize => write!(f, "isize"),
+            Self::F32 => write!(f, "f32"),
+            Self::F64 => write!(f, "f64"),
+            Self::Unit => write!(f, "unit"),
+            Self::Never => write!(f, "never"),
+            Self::This => write!(f, "This"),
+            Self::Str => write!(f, "str"),
+            Self::Ref {
+                ref_kind: RefKind::Immutable,
+                ty,
+            } => write!(f, "ref {ty}"),
+            Self::Ref {
+                ref_kind: RefKind::Mutable,
+                ty,
+            } => write!(f, "ref mut {ty}"),
+            Self::Ref {
+                ref_kind: RefKind::Pointer,
+                ty,
+            } => write!(f, "*{ty}"),
+            Self::Array { ty, len } => write!(f, "[{ty}, {len}]"),
+            Self::Slice(ty) => write!(f, "[{ty}]"),
+            Self::Option(ty) => write!(f, "?{ty}"),
+            Self::Range(ty) => write!(f, "..{ty}"),
+            Self::Func { params, ret } => {
+                write!(f, "func(")?;
+                for (idx, param) in params.iter().enumerate() {
+                    if idx != 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{param}")?;
+                }
+                write!(f, "): {ret}")
+            }
+            Self::Struct(fields) => {
+                write!(f, "struct {{ ")?;
+                for (idx, field) in fields.iter().enumerate() {
+                    if idx != 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}: {}", field.name, field.ty)?;
+                }
+                write!(f, " }}")
+            }
+            Self::Declared { path, generics } => {
+                write!(f, "{}", path.join("::"))?;
+                write_generics(f, generics)
+            }
+            Self::Operator { op, generics } => {
+                write!(f, "operator {}", op.as_str())?;
+                write_generics(f, generics)
+            }
+            Self::Generic(name) => write!(f, "{name}"),
+            Self::Unknown => write!(f, "{{unknown}}"),
+        };
+    }
+}
+
+fn write_generics(f: &mut std::fmt::Formatter<'_>, generics: &[ResolvedType]) -> std::fmt::Result {
+    if generics.is_empty() {
+        return Ok(());
+    }
+
+    write!(f, ":<")?;
+    for (idx, generic) in generics.iter().enumerate() {
+        if idx != 0 {
+            write!(f, ", ")?;
+        }
+        write!(f, "{generic}")?;
+    }
+    return write!(f, ">");
+}
+
+impl Scope<'_> {
+    /// `generics` are the names of the generic types visible where `ty` is written
+    pub fn resolve_type(
+        &self,
+        ty: &Type,
+        generics: &[Arc<str>],
+        errors: &mut Vec<SemanticError>,
+    ) -> ResolvedType {
+        let mut resolve = |ty: &Type| Box::new(self.resolve_type(ty, generics, errors));
+
+        return match &ty.kind {
+            TypeKind::Char => ResolvedType::Char,
+            TypeKind::Bool => ResolvedType::Bool,
+
+            TypeKind::U8 => ResolvedType::U8,
+            TypeKind::I8 => ResolvedType::I8,
+            TypeKind::U16 => ResolvedType::U16,
+            TypeKind::I16 => ResolvedType::I16,
+            TypeKind::U32 => ResolvedType::U32,
+            TypeKind::I32 => ResolvedType::I32,
+            TypeKind::U64 => ResolvedType::U64,
+            TypeKind::I64 => ResolvedType::I64,
+            TypeKind::Usize => ResolvedType::Usize,
+            TypeKind::Isize => ResolvedType::Isize,
+
+            TypeKind::F32 => ResolvedType::F32,
+            TypeKind::F64 => ResolvedType::F64,
+
+            TypeKind::Unit => ResolvedType::Unit,
+            TypeKind::Never => ResolvedType::Never,
+
+            TypeKind::This => ResolvedType::This,
+
+            TypeKind::Str => ResolvedType::Str,
+
+            TypeKind::Ref { ref_kind, ty } => ResolvedType::Ref {
+                ref_kind: *ref_kind,
+                ty: resolve(ty),
+            },
+            TypeKind::Array { ty, len } => ResolvedType::Array {
+                ty: resolve(ty),
+                len: *len,
+            },
+            TypeKind::Slice(ty) => ResolvedType::Slice(resolve(ty)),
+            TypeKind::Option(ty) => ResolvedType::Option(resolve(ty)),
+            TypeKind::Range(ty) => ResolvedType::Range(resolve(ty)),
+            TypeKind::Func { params, ret } => ResolvedType::Func {
+                params: params.iter().map(|it| *resolve(it)).collect(),
+                ret: match ret {
+                    Some(ret) => resolve(ret),
+                    None => Box::new(ResolvedType::Unit),
+                },
+            },
+            TypeKind::Struct(body) => ResolvedType::Struct(
+                body.params
+                    .iter()
+                    .map(|it| ResolvedField {
+                        name: it.name.clone(),
+                        ty: *resolve(&it.ty),
+                    })
+                    .collect(),
+            ),
+            TypeKind::UserDefined {
+                path,
+                generics: args,
+            } => {
+                let args: Vec<_> = args.iter().map(|it| *resolve(it)).collect();
+
+                if generics.contains(&path.path[0]) {
+                    if path.path.len() > 1 {
+                        // Associated types of generics are only known once they're instantiated
+                        return ResolvedType::Unknown;
+                    }
+
+                    if !args.is_empty() {
+                        errors.push(SemanticError::GenericArity {
+                            slice: ty.slice.clone(),
+                            expected: 0,
+                            found: args.len(),
+                        });
+                    }
+
+                    return ResolvedType::Generic(path.path[0].clone());
+                }
+
+                let resolved = match self.resolve(path) {
+                    Ok(resolved) => resolved,
+                    Err(error) => {
+                        errors.push(*error);
+                        return ResolvedType::Unknown;
+                    }
+                };
+
+                let symbol = &resolved.symbol.symbol;
+
+                let (
+                    None,
+                    SymbolKind::Struct(_)
+                    | SymbolKind::Enum(_)
+                    | SymbolKind::Union(_)
+                    | SymbolKind::Trait(_),
+                ) = (&resolved.member, &symbol.kind)
+                else {
+                    errors.push(SemanticError::NotAType { path: path.clone() });
+                    return ResolvedType::Unknown;
+                };
+
+                let expected = match &symbol.generics {
+                    Some(generics) => generics.tys.len(),
+                    None => 0,
+                };
+
+                if args.len() != expected {
+                    errors.push(SemanticError::GenericArity {
+                        slice: ty.slice.clone(),
+                        expected,
+                        found: args.len(),
+                    });
+                }
+
+                ResolvedType::Declared {
+                    path: symbol.path.clone(),
+                    generics: args,
+                }
+            }
+            TypeKind::Operator { op, generics: args } => {
+                let args: Vec<_> = args.iter().map(|it| *resolve(it)).collect();
+
+                if args.len() != op.arity() {
+                    errors.push(SemanticError::GenericArity {
+                        slice: ty.slice.clone(),
+                        expected: op.arity(),
+                        found: args.len(),
+                    });
+                }
+
+                ResolvedType::Operator {
+                    op: *op,
+                    generics: args,
+                }
+            }
+        };
+    }
+}
@@ -1,10 +1,14 @@
 pub mod parser;
+pub mod source;
 
 use std::{fmt::Debug, sync::Arc};
 
+use source::{FileId, Location, SourceMap};
+
 #[derive(Clone, PartialEq, Eq)]
 pub struct StringSlice {
     pub src: Arc<str>,
+    pub file: FileId,
     pub start: usize,
     pub end: usize,
 }
@@ -19,14 +23,24 @@ impl StringSlice {
     }
 
     pub fn merge(&self, other: &Self) -> Self {
+        debug_assert!(
+            self.file == other.file && Arc::ptr_eq(&self.src, &other.src),
+            "merging slices from different files"
+        );
+
         let start = usize::min(self.start, other.start);
         let end = usize::max(self.end, other.end);
         return Self {
             src: self.src.clone(),
+            file: self.file,
             start,
             end,
         };
     }
+
+    pub fn location<'a>(&self, sources: &'a SourceMap) -> Option<Location<'a>> {
+        return sources.location(self);
+    }
 }
 
 impl Debug for StringSlice {
@@ -43,6 +57,7 @@ impl ToStringSlice for Arc<str> {
     fn slice(&self, start: usize, end: usize) -> StringSlice {
         return StringSlice {
             src: self.clone(),
+            file: FileId::default(),
             start,
             end,
         };
@@ -53,7 +68,7 @@ impl ToStringSlice for Arc<str> {
 mod test {
     use std::sync::Arc;
 
-    use crate::string::{StringSlice, ToStringSlice};
+    use crate::string::{source::FileId, StringSlice, ToStringSlice};
 
     #[test]
     fn slice() {
@@ -62,9 +77,18 @@ mod test {
             s.slice(0, 2),
             StringSlice {
                 src: s,
+                file: FileId::default(),
                 start: 0,
                 end: 2
             }
         );
     }
+
+    #[test]
+    #[should_panic = "merging slices from different files"]
+    fn merge_across_files() {
+        let a: Arc<str> = "a".into();
+        let b: Arc<str> = "b".into();
+        a.slice(0, 1).merge(&b.slice(0, 1));
+    }
 }
@@ -1,18 +1,25 @@
 use std::sync::Arc;
 
-use super::{StringSlice, ToStringSlice};
+use super::{source::FileId, StringSlice};
 
 #[derive(Debug)]
 pub struct StringParser {
     pub src: Arc<str>,
+    pub file: FileId,
+    /// Byte offset into `src`, always on a char boundary
     idx: usize,
     idx_stack: Vec<usize>,
 }
 
 impl StringParser {
     pub fn new(src: Arc<str>) -> Self {
+        return Self::for_file(FileId::default(), src);
+    }
+
+    pub fn for_file(file: FileId, src: Arc<str>) -> Self {
         return Self {
             src,
+            file,
             idx: 0,
             idx_stack: vec![],
         };
@@ -22,6 +29,16 @@ impl StringParser {
         return self.idx;
     }
 
+    /// An empty slice at the end of the source, for errors about it ending too early
+    pub fn eof(&self) -> StringSlice {
+        return StringSlice {
+            src: self.src.clone(),
+            file: self.file,
+            start: self.src.len(),
+            end: self.src.len(),
+        };
+    }
+
     pub fn checkout(&mut self) {
         self.idx_stack.push(self.idx);
     }
@@ -29,7 +46,12 @@ impl StringParser {
     pub fn commit(&mut self) -> Option<StringSlice> {
         let start = self.idx_stack.pop()?;
 
-        return Some(self.src.slice(start, self.idx));
+        return Some(StringSlice {
+            src: self.src.clone(),
+            file: self.file,
+            start,
+            end: self.idx,
+        });
     }
 
     pub fn rollback(&mut self) -> bool {
@@ -42,13 +64,12 @@ impl StringParser {
     }
 
     pub fn curr(&self) -> Option<char> {
-        return self.src.chars().nth(self.idx);
+        return self.src[self.idx..].chars().next();
     }
 
     pub fn next(&mut self) -> Option<char> {
-        self.idx += 1;
-        if self.idx > self.src.len() {
-            self.idx = self.src.len();
+        if let Some(c) = self.curr() {
+            self.idx += c.len_utf8();
         }
         return self.curr();
     }
@@ -125,7 +146,7 @@ impl StringParser {
 mod test {
     use std::sync::Arc;
 
-    use crate::string::StringSlice;
+    use crate::string::{source::FileId, StringSlice};
 
     use super::StringParser;
 
@@ -138,6 +159,7 @@ mod test {
             parser.try_consume_str(&s),
             Some(StringSlice {
                 src: s.clone(),
+                file: FileId::default(),
                 start: 0,
                 end: s.len()
             })
@@ -157,6 +179,7 @@ mod test {
             parser.while_char('s'),
             Some(StringSlice {
                 src: s.clone(),
+                file: FileId::default(),
                 start: 0,
                 end: s.len()
             })
@@ -176,6 +199,7 @@ mod test {
             parser.while_func(char::is_alphabetic),
             Some(StringSlice {
                 src: s.clone(),
+                file: FileId::default(),
                 start: 0,
                 end: s.len()
             })
@@ -185,4 +209,24 @@ mod test {
 
         assert_eq!(parser.while_func(char::is_numeric), None);
     }
+
+    #[test]
+    fn non_ascii() {
+        let s: Arc<str> = "héllo wörld 😀!".into();
+        let mut parser = StringParser::new(s.clone());
+
+        let word = parser.while_func(char::is_alphabetic).unwrap();
+        assert_eq!(&*word.value(), "héllo");
+        assert_eq!(word.end, "héllo".len());
+
+        assert_eq!(parser.next(), Some('w'));
+        let word = parser.while_func(char::is_alphabetic).unwrap();
+        assert_eq!(&*word.value(), "wörld");
+
+        assert_eq!(parser.next(), Some('😀'));
+        assert_eq!(parser.next(), Some('!'));
+        assert_eq!(parser.next(), None);
+        assert_eq!(parser.next(), None);
+        assert_eq!(parser.idx(), s.len());
+    }
 }
@@ -0,0 +1,178 @@
+use std::{
+    fmt::Display,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use super::StringSlice;
+
+/// Identifies a file registered in a `SourceMap`, sources that were never registered use the
+/// default id
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct FileId(pub usize);
+
+#[derive(Debug, Clone)]
+pub struct Source {
+    pub path: PathBuf,
+    pub src: Arc<str>,
+    /// Byte offset of the start of every line
+    lines: Vec<usize>,
+}
+
+/// Every loaded file, so slices can be traced back to a path, line and column
+#[derive(Debug, Clone)]
+pub struct SourceMap {
+    files: Vec<Source>,
+}
+
+/// 1 based, columns count chars rather than bytes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct LineCol {
+    pub line: usize,
+    pub col: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Location<'a> {
+    pub file: FileId,
+    pub path: &'a Path,
+    pub start: LineCol,
+    pub end: LineCol,
+}
+
+impl Source {
+    pub fn new(path: PathBuf, src: Arc<str>) -> Self {
+        let lines = std::iter::once(0)
+            .chain(src.match_indices('\n').map(|(idx, _)| idx + 1))
+            .collect();
+
+        return Self { path, src, lines };
+    }
+
+    pub fn line_count(&self) -> usize {
+        return self.lines.len();
+    }
+
+    /// The text of a 1 based line, without its line break
+    pub fn line(&self, line: usize) -> Option<&str> {
+        let start = *self.lines.get(line.checked_sub(1)?)?;
+        let end = self.lines.get(line).map_or(self.src.len(), |it| it - 1);
+
+        return Some(self.src[start..end].trim_end_matches('\r'));
+    }
+
+    pub fn line_col(&self, offset: usize) -> LineCol {
+        let offset = offset.min(self.src.len());
+        let line = self.lines.partition_point(|it| *it <= offset);
+        let start = self.lines[line - 1];
+
+        return LineCol {
+            line,
+            col: self.src[start..offset].chars().count() + 1,
+        };
+    }
+}
+
+impl SourceMap {
+    pub fn new() -> Self {
+        return Self { files: vec![] };
+    }
+
+    pub fn add(&mut self, path: PathBuf, src: Arc<str>) -> FileId {
+        self.files.push(Source::new(path, src));
+        return FileId(self.files.len() - 1);
+    }
+
+    pub fn get(&self, file: FileId) -> Option<&Source> {
+        return self.files.get(file.0);
+    }
+
+    /// Finds the source a slice was made from, the id alone isn't enough for slices of
+    /// sources that were never registered
+    pub fn source(&self, slice: &StringSlice) -> Option<&Source> {
+        return self
+            .get(slice.file)
+            .filter(|it| Arc::ptr_eq(&it.src, &slice.src));
+    }
+
+    pub fn location(&self, slice: &StringSlice) -> Option<Location<'_>> {
+        let source = self.source(slice)?;
+
+        return Some(Location {
+            file: slice.file,
+            path: &source.path,
+            start: source.line_col(slice.start),
+            end: source.line_col(slice.end),
+        });
+    }
+}
+
+impl Default for SourceMap {
+    fn default() -> Self {
+        return Self::new();
+    }
+}
+
+impl Display for Location<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return write!(
+            f,
+            "{}:{}:{}",
+            self.path.display(),
+            self.start.line,
+            self.start.col
+        );
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::{Path, PathBuf};
+
+    use crate::tokenizer::{
+        token::{Keyword, TokenKind},
+        Tokenizer,
+    };
+
+    use super::{LineCol, SourceMap};
+
+    #[test]
+    fn locations() {
+        let mut sources = SourceMap::new();
+        let a = sources.add(
+            PathBuf::from("A.gek"),
+            "func Main() {\n  let é = 1;\r\n}".into(),
+        );
+        let b = sources.add(PathBuf::from("B.gek"), "\n\nlet x = 2;".into());
+
+        let source = sources.get(a).unwrap();
+        assert_eq!(source.line_count(), 3);
+        assert_eq!(source.line(2), Some("  let é = 1;"));
+        assert_eq!(source.line(4), None);
+
+        let mut tokenizer = Tokenizer::for_file(b, sources.get(b).unwrap().src.clone());
+        let token = tokenizer.next().unwrap();
+        assert_eq!(token.kind, TokenKind::Keyword(Keyword::Let));
+
+        let location = sources.location(&token.slice).unwrap();
+        assert_eq!(location.path, Path::new("B.gek"));
+        assert_eq!(location.start, LineCol { line: 3, col: 1 });
+        assert_eq!(location.end, LineCol { line: 3, col: 4 });
+        assert_eq!(location.to_string(), "B.gek:3:1");
+
+        let mut tokenizer = Tokenizer::for_file(a, sources.get(a).unwrap().src.clone());
+        for _ in 0..7 {
+            tokenizer.next().unwrap();
+        }
+        let token = tokenizer.next().unwrap();
+        assert_eq!(&*token.slice.value(), "=");
+
+        // Columns count chars, `é` is two bytes
+        let location = sources.location(&token.slice).unwrap();
+        assert_eq!(location.start, LineCol { line: 2, col: 9 });
+
+        // Slices of unregistered sources can't be located
+        let token = Tokenizer::new("x".into()).next().unwrap();
+        assert_eq!(sources.location(&token.slice), None);
+    }
+}
@@ -1,8 +1,11 @@
 use std::{collections::VecDeque, sync::Arc};
 
-use token::{Keyword, Number, Symbol, Token, TokenKind};
+use token::{Keyword, Number, NumberValue, OperatorTrait, Symbol, Token, TokenKind};
 
-use crate::string::{parser::StringParser, StringSlice};
+use crate::{
+    parse_tree::parse::error::ParserError,
+    string::{parser::StringParser, source::FileId, StringSlice},
+};
 
 pub mod token;
 
@@ -14,6 +17,39 @@ fn valid_ident_cont(c: char) -> bool {
     return c.is_alphanumeric() || c == '_';
 }
 
+fn is_indent(c: char) -> bool {
+    return c == ' ' || c == '\t';
+}
+
+fn valid_digit(c: char) -> bool {
+    return c.is_ascii_digit() || c == '_';
+}
+
+const NUMBER_SUFFIXES: &[Keyword] = &[
+    Keyword::U8,
+    Keyword::I8,
+    Keyword::U16,
+    Keyword::I16,
+    Keyword::U32,
+    Keyword::I32,
+    Keyword::U64,
+    Keyword::I64,
+    Keyword::Usize,
+    Keyword::Isize,
+    Keyword::F32,
+    Keyword::F64,
+];
+
+/// Parses digits with `_` separators, at least one digit is required
+fn parse_digits(digits: &str, radix: u32) -> Option<u64> {
+    let digits: String = digits.chars().filter(|it| *it != '_').collect();
+    if digits.is_empty() {
+        return None;
+    }
+
+    return u64::from_str_radix(&digits, radix).ok();
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum TokenizeError {
     InvalidString(StringSlice),
@@ -21,22 +57,38 @@ pub enum TokenizeError {
     UnclosedChar(StringSlice),
     InvalidEscape(StringSlice),
     UnclosedStr(StringSlice),
-    UnexpectedEof,
+    InvalidNumber(StringSlice),
+    InvalidSuffix(StringSlice),
+    UnexpectedEof(StringSlice),
 }
 
 pub struct Tokenizer {
     parser: StringParser,
     peek: VecDeque<Token>,
+    /// Errors the parser recovered from, they're reported once the whole file is parsed
+    pub errors: Vec<ParserError>,
 }
 
 impl Tokenizer {
     pub fn new(src: Arc<str>) -> Self {
+        return Self::for_file(FileId::default(), src);
+    }
+
+    /// Slices of the tokens will point back to `file` in the `SourceMap`
+    pub fn for_file(file: FileId, src: Arc<str>) -> Self {
         return Self {
-            parser: StringParser::new(src),
+            parser: StringParser::for_file(file, src),
             peek: VecDeque::new(),
+            errors: vec![],
         };
     }
 
+    /// An empty slice where the tokenizer currently is
+    pub fn empty_slice(&mut self) -> StringSlice {
+        self.parser.checkout();
+        return self.parser.commit().unwrap();
+    }
+
     fn try_parse_ident(&mut self) -> Option<StringSlice> {
         if self.parser.is_func(valid_ident_start) {
             return self.parser.while_func(valid_ident_cont);
@@ -44,35 +96,181 @@ impl Tokenizer {
         return None;
     }
 
-    fn try_parse_number(&mut self) -> Option<(StringSlice, Number)> {
+    /// A lone `_`, so `_x` doesn't count as an operand
+    fn try_parse_operand(&mut self) -> bool {
+        if !self.parser.is_char('_') {
+            return false;
+        }
+
         self.parser.checkout();
+        self.parser.next();
+        if self.parser.is_func(valid_ident_cont) {
+            self.parser.rollback();
+            return false;
+        }
 
-        if let Some(whole_slice) = self.parser.while_func(char::is_numeric) {
-            let whole: u64 = whole_slice.value().parse().unwrap();
+        self.parser.commit();
+        return true;
+    }
 
-            if self.parser.is_char('.') {
-                self.parser.checkout();
-                self.parser.next();
+    /// Operator trait names like `_+_`, `~_` or `_!`, where there's no space between the `_`s
+    /// and the operator
+    fn try_parse_operator_trait(&mut self) -> Option<(StringSlice, OperatorTrait)> {
+        self.parser.checkout();
 
-                if let Some(_) = self.parser.while_func(char::is_numeric) {
-                    let decimal = self.parser.commit()?.value().parse().unwrap();
-                    return Some((self.parser.commit()?, Number { whole, decimal }));
-                }
-                self.parser.rollback();
+        let lhs = self.try_parse_operand();
+        let symbol = Symbol::from(&mut self.parser).map(|(_, it)| it);
+        let rhs = symbol.is_some() && self.try_parse_operand();
+
+        let op = match (lhs, symbol, rhs) {
+            (true, Some(symbol), true) => OperatorTrait::binary(symbol),
+            (false, Some(symbol), true) => OperatorTrait::prefix(symbol),
+            (true, Some(symbol), false) => OperatorTrait::postfix(symbol),
+            _ => None,
+        };
+
+        let Some(op) = op else {
+            self.parser.rollback();
+            return None;
+        };
+
+        return Some((self.parser.commit().unwrap(), op));
+    }
+
+    fn try_parse_number(&mut self) -> Result<Option<(StringSlice, Number)>, TokenizeError> {
+        if !self.parser.is_func(|it| it.is_ascii_digit()) {
+            return Ok(None);
+        }
+
+        self.parser.checkout();
+
+        for (prefix, radix) in [("0x", 16), ("0b", 2), ("0o", 8)] {
+            if self.parser.try_consume_str(prefix).is_none() {
+                continue;
             }
 
-            return Some((
-                self.parser.commit()?,
+            // Everything that could continue the literal is part of it, so `0b102` is an error
+            let Some(rest) = self.parser.while_func(valid_ident_cont) else {
+                return Err(TokenizeError::InvalidNumber(self.parser.commit().unwrap()));
+            };
+            let slice = self.parser.commit().unwrap();
+
+            // `f` is a hex digit, so only integer suffixes are allowed after a prefix
+            let rest = rest.value();
+            let (digits, suffix) = match rest.find(['u', 'i']) {
+                Some(idx) => rest.split_at(idx),
+                None => (&*rest, ""),
+            };
+
+            let Some(value) = parse_digits(digits, radix) else {
+                return Err(TokenizeError::InvalidNumber(slice));
+            };
+
+            let suffix = match suffix {
+                "" => None,
+                suffix => match Keyword::from(suffix) {
+                    Some(keyword) if NUMBER_SUFFIXES.contains(&keyword) => Some(keyword),
+                    _ => {
+                        return Err(TokenizeError::InvalidSuffix(StringSlice {
+                            start: slice.end - suffix.len(),
+                            ..slice.clone()
+                        }))
+                    }
+                },
+            };
+
+            return Ok(Some((
+                slice,
                 Number {
-                    whole,
-                    decimal: 0.0,
+                    text: format!("{prefix}{digits}").into(),
+                    value: NumberValue::Int(value),
+                    suffix,
                 },
-            ));
+            )));
         }
 
-        self.parser.rollback();
+        self.parser.checkout();
+        self.parser.while_func(valid_digit);
 
-        return None;
+        let mut is_float = false;
+
+        if self.parser.is_char('.') {
+            self.parser.checkout();
+            self.parser.next();
+
+            match self.parser.while_func(valid_digit) {
+                Some(_) => {
+                    self.parser.commit();
+                    is_float = true;
+                }
+                None => {
+                    self.parser.rollback();
+                }
+            }
+        }
+
+        if self.parser.is_char('e') || self.parser.is_char('E') {
+            self.parser.checkout();
+            self.parser.next();
+
+            if self.parser.is_char('+') || self.parser.is_char('-') {
+                self.parser.next();
+            }
+
+            // `1e_` isn't an exponent, the separators need a digit to separate
+            let digits = self.parser.while_func(valid_digit);
+            match digits.is_some_and(|it| it.value().contains(|c: char| c.is_ascii_digit())) {
+                true => {
+                    self.parser.commit();
+                    is_float = true;
+                }
+                false => {
+                    self.parser.rollback();
+                }
+            }
+        }
+
+        let text = self.parser.commit().unwrap().value();
+
+        let suffix = match self.parser.is_func(valid_ident_start) {
+            true => self.parser.while_func(valid_ident_cont),
+            false => None,
+        };
+
+        let slice = self.parser.commit().unwrap();
+
+        let value = match is_float {
+            true => {
+                let digits: String = text.chars().filter(|it| *it != '_').collect();
+                match digits.parse() {
+                    Ok(value) => NumberValue::Float(value),
+                    Err(_) => return Err(TokenizeError::InvalidNumber(slice)),
+                }
+            }
+            false => match parse_digits(&text, 10) {
+                Some(value) => NumberValue::Int(value),
+                None => return Err(TokenizeError::InvalidNumber(slice)),
+            },
+        };
+
+        let suffix = match suffix {
+            Some(suffix) => match Keyword::from(&suffix.value()) {
+                // Float literals can't be typed as integers
+                Some(keyword @ (Keyword::F32 | Keyword::F64)) => Some(keyword),
+                Some(keyword) if NUMBER_SUFFIXES.contains(&keyword) && !is_float => Some(keyword),
+                _ => return Err(TokenizeError::InvalidSuffix(suffix)),
+            },
+            None => None,
+        };
+
+        return Ok(Some((
+            slice,
+            Number {
+                text,
+                value,
+                suffix,
+            },
+        )));
     }
 
     fn try_parse_char(&mut self) -> Result<Option<(StringSlice, char)>, TokenizeError> {
@@ -83,96 +281,191 @@ impl Tokenizer {
 
         self.parser.next();
 
-        let Some(c) = self.parser.curr() else {
-            self.parser.rollback();
-            return Err(TokenizeError::UnexpectedEof);
+        let c = match self.parser.curr() {
+            Some('\\') => self.parse_escape()?,
+            Some('\'' | '\n') => {
+                let Some(s) = self.parser.commit() else {
+                    return Err(TokenizeError::UnexpectedEof(self.parser.eof()));
+                };
+                return Err(TokenizeError::UnclosedChar(s));
+            }
+            Some(c) => {
+                self.parser.next();
+                c
+            }
+            None => {
+                self.parser.rollback();
+                return Err(TokenizeError::UnexpectedEof(self.parser.eof()));
+            }
         };
 
-        match c {
-            'a'..='z'
-            | 'A'..='Z'
-            | '0'..='9'
-            | ' '
-            | '!'
-            | '#'
-            | '%'
-            | '&'
-            | '"'
-            | '('
-            | ')'
-            | '['
-            | ']'
-            | '{'
-            | '}'
-            | '*'
-            | '+'
-            | ','
-            | '-'
-            | '.'
-            | '/'
-            | ':'
-            | ';'
-            | '<'
-            | '>'
-            | '='
-            | '?'
-            | '^'
-            | '_'
-            | '|'
-            | '~' => {
-                let Some('\'') = self.parser.next() else {
-                    let Some(s) = self.parser.commit() else {
-                        return Err(TokenizeError::UnexpectedEof);
-                    };
-                    return Err(TokenizeError::UnclosedChar(s));
-                };
+        if !self.parser.is_char('\'') {
+            let Some(s) = self.parser.commit() else {
+                return Err(TokenizeError::UnexpectedEof(self.parser.eof()));
+            };
+            return Err(TokenizeError::UnclosedChar(s));
+        }
+
+        self.parser.next();
+        let Some(s) = self.parser.commit() else {
+            return Err(TokenizeError::UnexpectedEof(self.parser.eof()));
+        };
+        return Ok(Some((s, c)));
+    }
 
+    /// Parses the escape sequence starting at the current `\`,
+    /// errors only span the escape sequence itself
+    fn parse_escape(&mut self) -> Result<char, TokenizeError> {
+        self.parser.checkout();
+
+        let Some(c) = self.parser.next() else {
+            return Err(TokenizeError::UnexpectedEof(self.parser.eof()));
+        };
+        self.parser.next();
+
+        let val = match c {
+            'n' => Some('\n'),
+            'r' => Some('\r'),
+            't' => Some('\t'),
+            '0' => Some('\0'),
+            '\\' => Some('\\'),
+            '"' => Some('"'),
+            '\'' => Some('\''),
+            // Only ASCII, anything above has to be written as a unicode escape
+            'x' => match self.parse_hex_byte() {
+                Some(value) if value <= 0x7F => Some(value as char),
+                _ => None,
+            },
+            'u' if self.parser.is_char('{') => {
                 self.parser.next();
-                let Some(s) = self.parser.commit() else {
-                    return Err(TokenizeError::UnexpectedEof);
-                };
-                return Ok(Some((s, c)));
-            }
-            '\\' => {
-                let Some(c) = self.parser.next() else {
-                    return Err(TokenizeError::UnexpectedEof);
-                };
-                let val = match c {
-                    'n' => '\n',
-                    'r' => '\r',
-                    '\\' => '\\',
-                    't' => '\t',
-                    '"' => '"',
-                    '\'' => '\'',
-                    _ => {
-                        let Some(s) = self.parser.commit() else {
-                            return Err(TokenizeError::UnexpectedEof);
-                        };
+                let digits = self.parser.while_func(|it| it.is_ascii_hexdigit());
+
+                match (digits, self.parser.is_char('}')) {
+                    (Some(digits), true) => {
+                        self.parser.next();
 
-                        return Err(TokenizeError::InvalidEscape(s));
+                        // Surrogates and values above 0x10FFFF aren't chars
+                        let digits = digits.value();
+                        match digits.len() <= 6 {
+                            true => u32::from_str_radix(&digits, 16)
+                                .ok()
+                                .and_then(char::from_u32),
+                            false => None,
+                        }
                     }
-                };
+                    _ => None,
+                }
+            }
+            _ => None,
+        };
+
+        let Some(s) = self.parser.commit() else {
+            return Err(TokenizeError::UnexpectedEof(self.parser.eof()));
+        };
+
+        return match val {
+            Some(val) => Ok(val),
+            None => Err(TokenizeError::InvalidEscape(s)),
+        };
+    }
+
+    /// The two hex digits of a `\x` escape
+    fn parse_hex_byte(&mut self) -> Option<u8> {
+        let mut digits = String::new();
+        while digits.len() < 2
+            && let Some(c) = self.parser.curr()
+            && c.is_ascii_hexdigit()
+        {
+            digits.push(c);
+            self.parser.next();
+        }
+
+        if digits.len() != 2 {
+            return None;
+        }
+        return u8::from_str_radix(&digits, 16).ok();
+    }
+
+    /// Like `parse_escape`, but `\x` can be any byte, other chars are utf8 encoded
+    fn parse_byte_escape(&mut self, bytes: &mut Vec<u8>) -> Result<(), TokenizeError> {
+        self.parser.checkout();
+
+        if self.parser.try_consume_str("\\x").is_none() {
+            self.parser.rollback();
+            let c = self.parse_escape()?;
+            bytes.extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes());
+            return Ok(());
+        }
+
+        let byte = self.parse_hex_byte();
+        let Some(s) = self.parser.commit() else {
+            return Err(TokenizeError::UnexpectedEof(self.parser.eof()));
+        };
+
+        let Some(byte) = byte else {
+            return Err(TokenizeError::InvalidEscape(s));
+        };
+        bytes.push(byte);
+
+        return Ok(());
+    }
+
+    /// `b"..."` and `c"..."`, the contents of `c` strings can't contain a null byte
+    fn try_parse_bytes(
+        &mut self,
+        prefix: char,
+    ) -> Result<Option<(StringSlice, Vec<u8>)>, TokenizeError> {
+        if !self.parser.is_char(prefix) {
+            return Ok(None);
+        }
+
+        self.parser.checkout();
+        self.parser.next();
+
+        if !self.parser.is_char('"') {
+            self.parser.rollback();
+            return Ok(None);
+        }
+        self.parser.next();
+
+        let mut bytes = vec![];
 
-                let Some('\'') = self.parser.next() else {
+        while let Some(c) = self.parser.curr() {
+            match c {
+                '\\' => {
+                    self.parser.checkout();
+                    let len = bytes.len();
+                    self.parse_byte_escape(&mut bytes)?;
+                    let escape = self.parser.commit().unwrap();
+
+                    if prefix == 'c' && bytes[len..].contains(&0) {
+                        return Err(TokenizeError::InvalidEscape(escape));
+                    }
+                }
+                '"' => {
+                    self.parser.next();
                     let Some(s) = self.parser.commit() else {
-                        return Err(TokenizeError::UnexpectedEof);
+                        return Err(TokenizeError::UnexpectedEof(self.parser.eof()));
                     };
-                    return Err(TokenizeError::UnclosedChar(s));
-                };
 
-                self.parser.next();
-                let Some(s) = self.parser.commit() else {
-                    return Err(TokenizeError::UnexpectedEof);
-                };
-                return Ok(Some((s, val)));
-            }
-            _ => {
-                let Some(s) = self.parser.commit() else {
-                    return Err(TokenizeError::UnexpectedEof);
-                };
-                return Err(TokenizeError::UnclosedChar(s));
+                    return Ok(Some((s, bytes)));
+                }
+                '\n' => {
+                    let Some(s) = self.parser.commit() else {
+                        return Err(TokenizeError::UnexpectedEof(self.parser.eof()));
+                    };
+                    return Err(TokenizeError::UnclosedStr(s));
+                }
+                _ => {
+                    bytes.extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes());
+                    self.parser.next();
+                }
             }
         }
+
+        self.parser.rollback();
+
+        return Err(TokenizeError::UnexpectedEof(self.parser.eof()));
     }
 
     fn try_parse_string(&mut self) -> Result<Option<(StringSlice, String)>, TokenizeError> {
@@ -188,89 +481,145 @@ impl Tokenizer {
 
         while let Some(c) = self.parser.curr() {
             match c {
-                'a'..='z'
-                | 'A'..='Z'
-                | '0'..='9'
-                | ' '
-                | '!'
-                | '#'
-                | '%'
-                | '&'
-                | '\''
-                | '('
-                | ')'
-                | '['
-                | ']'
-                | '{'
-                | '}'
-                | '*'
-                | '+'
-                | ','
-                | '-'
-                | '.'
-                | '/'
-                | ':'
-                | ';'
-                | '<'
-                | '>'
-                | '='
-                | '?'
-                | '^'
-                | '_'
-                | '|'
-                | '~' => {
-                    str.push(c);
-                    self.parser.next();
-                }
                 '\\' => {
-                    let Some(c) = self.parser.next() else {
-                        return Err(TokenizeError::UnexpectedEof);
-                    };
-                    let val = match c {
-                        'n' => '\n',
-                        'r' => '\r',
-                        '\\' => '\\',
-                        't' => '\t',
-                        '"' => '"',
-                        '\'' => '\'',
-                        _ => {
-                            let Some(s) = self.parser.commit() else {
-                                return Err(TokenizeError::UnexpectedEof);
-                            };
-
-                            return Err(TokenizeError::InvalidEscape(s));
-                        }
-                    };
-
-                    str.push(val);
-                    self.parser.next();
+                    str.push(self.parse_escape()?);
                 }
                 '"' => {
                     self.parser.next();
                     let Some(s) = self.parser.commit() else {
-                        return Err(TokenizeError::UnexpectedEof);
+                        return Err(TokenizeError::UnexpectedEof(self.parser.eof()));
                     };
 
                     return Ok(Some((s, str)));
                 }
                 '\n' => {
                     let Some(s) = self.parser.commit() else {
-                        return Err(TokenizeError::UnexpectedEof);
+                        return Err(TokenizeError::UnexpectedEof(self.parser.eof()));
                     };
                     return Err(TokenizeError::UnclosedStr(s));
                 }
                 _ => {
-                    let Some(s) = self.parser.commit() else {
-                        return Err(TokenizeError::UnexpectedEof);
-                    };
-                    return Err(TokenizeError::InvalidString(s));
+                    str.push(c);
+                    self.parser.next();
                 }
             }
         }
 
         self.parser.rollback();
 
-        return Err(TokenizeError::UnexpectedEof);
+        return Err(TokenizeError::UnexpectedEof(self.parser.eof()));
+    }
+
+    fn try_parse_raw_string(&mut self) -> Result<Option<(StringSlice, String)>, TokenizeError> {
+        if !self.parser.is_char('r') {
+            return Ok(None);
+        }
+
+        self.parser.checkout();
+        self.parser.next();
+
+        // `r#"..."#` allows quotes inside, as long as they aren't followed by as many `#`s
+        let hashes = self.parser.while_char('#').map_or(0, |it| it.value().len());
+
+        if !self.parser.is_char('"') {
+            self.parser.rollback();
+            return Ok(None);
+        }
+        self.parser.next();
+
+        let closing = format!("\"{}", "#".repeat(hashes));
+        let start = self.parser.idx();
+
+        loop {
+            let end = self.parser.idx();
+
+            if self.parser.try_consume_str(&closing).is_some() {
+                let s = self.parser.commit().unwrap();
+                let str = self.parser.src[start..end].to_string();
+                return Ok(Some((s, str)));
+            }
+
+            if self.parser.curr().is_none() {
+                return Err(TokenizeError::UnclosedStr(self.parser.commit().unwrap()));
+            }
+            self.parser.next();
+        }
+    }
+
+    /// `"""` followed by a new line starts a multi-line string that ends at the next `"""`.
+    /// The common indentation of all non blank lines is stripped, and so is the line with the
+    /// closing quotes if there's nothing else on it
+    fn try_parse_multiline_string(
+        &mut self,
+    ) -> Result<Option<(StringSlice, String)>, TokenizeError> {
+        self.parser.checkout();
+
+        if self.parser.try_consume_str("\"\"\"").is_none() {
+            self.parser.rollback();
+            return Ok(None);
+        }
+
+        self.parser.while_func(is_indent);
+        if !self.parser.is_char('\n') {
+            return Err(TokenizeError::InvalidString(self.parser.commit().unwrap()));
+        }
+        self.parser.next();
+
+        let mut lines: Vec<(Arc<str>, String)> = vec![];
+
+        let last = 'lines: loop {
+            let indent = match self.parser.while_func(is_indent) {
+                Some(indent) => indent.value(),
+                None => "".into(),
+            };
+            let mut line = String::new();
+
+            loop {
+                match self.parser.curr() {
+                    Some('\n') => {
+                        self.parser.next();
+                        break;
+                    }
+                    Some('\\') => {
+                        line.push(self.parse_escape()?);
+                    }
+                    Some('"') if self.parser.try_consume_str("\"\"\"").is_some() => {
+                        break 'lines (indent, line);
+                    }
+                    Some(c) => {
+                        line.push(c);
+                        self.parser.next();
+                    }
+                    None => {
+                        return Err(TokenizeError::UnclosedStr(self.parser.commit().unwrap()));
+                    }
+                }
+            }
+
+            lines.push((indent, line));
+        };
+
+        if !last.1.is_empty() {
+            lines.push(last);
+        }
+
+        let strip = lines
+            .iter()
+            .filter(|(_, line)| !line.is_empty())
+            .map(|(indent, _)| indent.len())
+            .min()
+            .unwrap_or(0);
+
+        let lines: Vec<String> = lines
+            .into_iter()
+            .map(|(indent, line)| match line.is_empty() {
+                true => line,
+                false => format!("{}{line}", &indent[strip..]),
+            })
+            .collect();
+
+        let s = self.parser.commit().unwrap();
+        return Ok(Some((s, lines.join("\n"))));
     }
 
     fn skip_ignores(&mut self) -> Result<(), TokenizeError> {
@@ -288,7 +637,7 @@ impl Tokenizer {
             if self.parser.try_consume_str("/*").is_some() {
                 while self.parser.try_consume_str("*/").is_none() {
                     if self.parser.curr().is_none() {
-                        return Err(TokenizeError::UnexpectedEof);
+                        return Err(TokenizeError::UnexpectedEof(self.parser.eof()));
                     }
                     self.parser.next();
                 }
@@ -334,6 +683,35 @@ impl Tokenizer {
             });
         }
 
+        if let Some((slice, str)) = self.try_parse_raw_string()? {
+            return Ok(Token {
+                slice,
+                kind: TokenKind::String(str.into()),
+            });
+        }
+
+        if let Some((slice, bytes)) = self.try_parse_bytes('b')? {
+            return Ok(Token {
+                slice,
+                kind: TokenKind::ByteString(bytes.into()),
+            });
+        }
+
+        if let Some((slice, mut bytes)) = self.try_parse_bytes('c')? {
+            bytes.push(0);
+            return Ok(Token {
+                slice,
+                kind: TokenKind::CString(bytes.into()),
+            });
+        }
+
+        if let Some((slice, op)) = self.try_parse_operator_trait() {
+            return Ok(Token {
+                slice,
+                kind: TokenKind::OperatorTrait(op),
+            });
+        }
+
         if let Some(slice) = self.try_parse_ident() {
             let value = slice.value();
 
@@ -357,7 +735,7 @@ impl Tokenizer {
             });
         }
 
-        if let Some((slice, number)) = self.try_parse_number() {
+        if let Some((slice, number)) = self.try_parse_number()? {
             return Ok(Token {
                 slice,
                 kind: TokenKind::Number(number),
@@ -371,6 +749,13 @@ impl Tokenizer {
             });
         }
 
+        if let Some((slice, str)) = self.try_parse_multiline_string()? {
+            return Ok(Token {
+                slice,
+                kind: TokenKind::String(str.into()),
+            });
+        }
+
         if let Some((slice, str)) = self.try_parse_string()? {
             return Ok(Token {
                 slice,
@@ -378,6 +763,380 @@ impl Tokenizer {
             });
         }
 
+        self.parser.checkout();
+        self.parser.next();
         return Err(TokenizeError::InvalidChar(self.parser.commit().unwrap()));
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::{
+        sync::Arc,
+        time::{Duration, Instant},
+    };
+
+    use super::{
+        token::{Keyword, Number, NumberValue, OperatorTrait, Symbol, TokenKind},
+        TokenizeError, Tokenizer,
+    };
+
+    fn number(src: &str) -> Result<Number, TokenizeError> {
+        let token = Tokenizer::new(src.into()).next()?;
+        let TokenKind::Number(number) = token.kind else {
+            panic!("expected a number, got {token:?}");
+        };
+        assert_eq!(&*token.slice.value(), src);
+
+        return Ok(number);
+    }
+
+    fn int(src: &str) -> Result<u64, TokenizeError> {
+        let NumberValue::Int(value) = number(src)?.value else {
+            panic!("expected {src} to be an integer");
+        };
+        return Ok(value);
+    }
+
+    fn float(src: &str) -> Result<f64, TokenizeError> {
+        let NumberValue::Float(value) = number(src)?.value else {
+            panic!("expected {src} to be a float");
+        };
+        return Ok(value);
+    }
+
+    #[test]
+    fn radix_numbers() -> Result<(), TokenizeError> {
+        assert_eq!(int("0x10")?, 16);
+        assert_eq!(int("0x0000_00fF")?, 255);
+        assert_eq!(int("0b1010_1010")?, 170);
+        assert_eq!(int("0o17")?, 15);
+        assert_eq!(int("1_000_000")?, 1_000_000);
+        assert_eq!(int("0xFFFF_FFFF_FFFF_FFFF")?, u64::MAX);
+
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_numbers() {
+        for src in [
+            "0x",
+            "0b_",
+            "0b102",
+            "0o8",
+            "0xG",
+            "0x1_0000_0000_0000_0000",
+            "18446744073709551616",
+        ] {
+            let Err(TokenizeError::InvalidNumber(slice)) = Tokenizer::new(src.into()).next() else {
+                panic!("expected {src} to be an invalid number");
+            };
+            assert_eq!(&*slice.value(), src);
+        }
+    }
+
+    #[test]
+    fn suffixes() -> Result<(), TokenizeError> {
+        for (src, text, suffix) in [
+            ("255u8", "255", Keyword::U8),
+            ("1i64", "1", Keyword::I64),
+            ("0.5f32", "0.5", Keyword::F32),
+            ("1_000usize", "1_000", Keyword::Usize),
+            ("0xFFu8", "0xFF", Keyword::U8),
+            ("0b1i8", "0b1", Keyword::I8),
+            ("1e3f64", "1e3", Keyword::F64),
+        ] {
+            let number = number(src)?;
+            assert_eq!(&*number.text, text);
+            assert_eq!(number.suffix, Some(suffix));
+        }
+
+        for (src, suffix) in [
+            ("10u7", "u7"),
+            ("1.5u8", "u8"),
+            ("3abc", "abc"),
+            ("0x1u3", "u3"),
+        ] {
+            let Err(TokenizeError::InvalidSuffix(slice)) = Tokenizer::new(src.into()).next() else {
+                panic!("expected {src} to have an invalid suffix");
+            };
+            assert_eq!(&*slice.value(), suffix);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn floats() -> Result<(), TokenizeError> {
+        assert_eq!(float("1.05")?, 1.05);
+        assert_eq!(float("1.5")?, 1.5);
+        assert_ne!(float("1.05")?, float("1.5")?);
+        assert_eq!(float("0.000_1")?, 0.0001);
+        assert_eq!(float("1e-9")?, 1e-9);
+        assert_eq!(float("2.5E+3")?, 2500.0);
+        assert_eq!(float("1E10")?, 1e10);
+        assert_eq!(&*number("1.050")?.text, "1.050");
+
+        // Without digits after them the dot and the `e` aren't part of the number
+        let mut tokenizer = Tokenizer::new("1.Size".into());
+        assert_eq!(tokenizer.next()?.kind, TokenKind::Number(number("1")?));
+        assert!(matches!(
+            Tokenizer::new("2else".into()).next(),
+            Err(TokenizeError::InvalidSuffix(_))
+        ));
+
+        // Separators alone aren't an exponent either
+        for src in ["1e_", "1.5e+_", "2E-__"] {
+            assert!(matches!(
+                Tokenizer::new(src.into()).next(),
+                Err(TokenizeError::InvalidSuffix(_))
+            ));
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn unicode() -> Result<(), TokenizeError> {
+        let mut tokenizer = Tokenizer::new("'é' '$' '😀' \"h€llo $@ wörld\" after".into());
+
+        for c in ['é', '$', '😀'] {
+            assert_eq!(tokenizer.next()?.kind, TokenKind::Char(c));
+        }
+
+        let token = tokenizer.next()?;
+        assert_eq!(token.kind, TokenKind::String("h€llo $@ wörld".into()));
+        assert_eq!(&*token.slice.value(), "\"h€llo $@ wörld\"");
+
+        let token = tokenizer.next()?;
+        assert_eq!(&*token.slice.value(), "after");
+
+        Ok(())
+    }
+
+    #[test]
+    fn escapes() -> Result<(), TokenizeError> {
+        let mut tokenizer = Tokenizer::new(r#""\u{1F600}\x7F\0\n\"" '\u{e9}' '\x41' '\''"#.into());
+
+        assert_eq!(
+            tokenizer.next()?.kind,
+            TokenKind::String("\u{1F600}\x7F\0\n\"".into())
+        );
+        assert_eq!(tokenizer.next()?.kind, TokenKind::Char('é'));
+        assert_eq!(tokenizer.next()?.kind, TokenKind::Char('A'));
+        assert_eq!(tokenizer.next()?.kind, TokenKind::Char('\''));
+
+        for (src, escape) in [
+            (r#""ab\u{D800}cd""#, r"\u{D800}"),
+            (r#""\u{110000}""#, r"\u{110000}"),
+            (r#""\u{0000001}""#, r"\u{0000001}"),
+            (r#""\u{}""#, r"\u{"),
+            (r#""\u41""#, r"\u"),
+            ("'\\x80'", r"\x80"),
+            ("'\\x4'", r"\x4"),
+            (r#""\q""#, r"\q"),
+        ] {
+            let Err(TokenizeError::InvalidEscape(slice)) = Tokenizer::new(src.into()).next() else {
+                panic!("expected {src} to have an invalid escape");
+            };
+            assert_eq!(&*slice.value(), escape);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn raw_strings() -> Result<(), TokenizeError> {
+        let src = r####"r"C:\path\n" r#"say "hi"
+to \u{0}"# r##"a "# b"## r x"####;
+        let mut tokenizer = Tokenizer::new(src.into());
+
+        let token = tokenizer.next()?;
+        assert_eq!(token.kind, TokenKind::String(r"C:\path\n".into()));
+        assert_eq!(&*token.slice.value(), r#"r"C:\path\n""#);
+
+        let token = tokenizer.next()?;
+        assert_eq!(
+            token.kind,
+            TokenKind::String("say \"hi\"\nto \\u{0}".into())
+        );
+        assert_eq!(token.slice.end, src.find(" r##").unwrap());
+
+        assert_eq!(
+            tokenizer.next()?.kind,
+            TokenKind::String(r##"a "# b"##.into())
+        );
+        assert_eq!(tokenizer.next()?.kind, TokenKind::Identifier("r".into()));
+        assert_eq!(tokenizer.next()?.kind, TokenKind::Identifier("x".into()));
+
+        let Err(TokenizeError::UnclosedStr(slice)) = Tokenizer::new(r##"r#"abc" "##.into()).next()
+        else {
+            panic!("expected an unclosed raw string");
+        };
+        assert_eq!(&*slice.value(), r##"r#"abc" "##);
+
+        Ok(())
+    }
+
+    #[test]
+    fn multiline_strings() -> Result<(), TokenizeError> {
+        let src = "let x = \"\"\"\n        SELECT *\n\n          FROM \"t\"\\t\n        \"\"\"; y";
+        let mut tokenizer = Tokenizer::new(src.into());
+        for _ in 0..3 {
+            tokenizer.next()?;
+        }
+
+        let token = tokenizer.next()?;
+        assert_eq!(
+            token.kind,
+            TokenKind::String("SELECT *\n\n  FROM \"t\"\t".into())
+        );
+        assert_eq!(token.slice.start, src.find('"').unwrap());
+        assert_eq!(token.slice.end, src.find(';').unwrap());
+
+        let mut tokenizer = Tokenizer::new("\"\"\"\n  a\n    b\"\"\"".into());
+        assert_eq!(tokenizer.next()?.kind, TokenKind::String("a\n  b".into()));
+
+        let mut tokenizer = Tokenizer::new("\"\" x".into());
+        assert_eq!(tokenizer.next()?.kind, TokenKind::String("".into()));
+
+        assert!(matches!(
+            Tokenizer::new("\"\"\"a\"\"\"".into()).next(),
+            Err(TokenizeError::InvalidString(_))
+        ));
+        assert!(matches!(
+            Tokenizer::new("\"\"\"\n a\n".into()).next(),
+            Err(TokenizeError::UnclosedStr(_))
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn byte_strings() -> Result<(), TokenizeError> {
+        let mut tokenizer = Tokenizer::new(r#"b"hi\xFF\n" c"é\0" c"hi" b x"#.into());
+
+        let token = tokenizer.next()?;
+        assert_eq!(token.kind, TokenKind::ByteString(b"hi\xFF\n"[..].into()));
+        assert_eq!(&*token.slice.value(), r#"b"hi\xFF\n""#);
+
+        let Err(TokenizeError::InvalidEscape(slice)) = tokenizer.next() else {
+            panic!("expected a null byte in a c string to be rejected");
+        };
+        assert_eq!(&*slice.value(), r"\0");
+
+        let mut tokenizer = Tokenizer::new(r#"c"hé" b x"#.into());
+        assert_eq!(
+            tokenizer.next()?.kind,
+            TokenKind::CString("hé\0".as_bytes().into())
+        );
+        assert_eq!(tokenizer.next()?.kind, TokenKind::Identifier("b".into()));
+        assert_eq!(tokenizer.next()?.kind, TokenKind::Identifier("x".into()));
+
+        Ok(())
+    }
+
+    #[test]
+    fn operator_traits() -> Result<(), TokenizeError> {
+        let mut tokenizer = Tokenizer::new("_+_ _<<_ ~_ -_ _! _?".into());
+        for op in [
+            OperatorTrait::Add,
+            OperatorTrait::Shl,
+            OperatorTrait::BitNot,
+            OperatorTrait::Neg,
+            OperatorTrait::Cascade,
+            OperatorTrait::Coalesce,
+        ] {
+            assert_eq!(tokenizer.next()?.kind, TokenKind::OperatorTrait(op));
+        }
+
+        // Anything else next to a `_` is still a discard or an identifier
+        let mut tokenizer = Tokenizer::new("_ + _ _=>_+_x _.x".into());
+        let kinds = [
+            TokenKind::Keyword(Keyword::Discard),
+            TokenKind::Symbol(Symbol::Add),
+            TokenKind::Keyword(Keyword::Discard),
+            TokenKind::Keyword(Keyword::Discard),
+            TokenKind::Symbol(Symbol::WideArrow),
+            TokenKind::Keyword(Keyword::Discard),
+            TokenKind::Symbol(Symbol::Add),
+            TokenKind::Identifier("_x".into()),
+            TokenKind::Keyword(Keyword::Discard),
+            TokenKind::Symbol(Symbol::Dot),
+            TokenKind::Identifier("x".into()),
+        ];
+        for kind in kinds {
+            assert_eq!(tokenizer.next()?.kind, kind);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn not_equal() -> Result<(), TokenizeError> {
+        let mut tokenizer = Tokenizer::new("a != b !c _!=".into());
+        let kinds = [
+            TokenKind::Identifier("a".into()),
+            TokenKind::Symbol(Symbol::NotEqual),
+            TokenKind::Identifier("b".into()),
+            TokenKind::Symbol(Symbol::BoolNot),
+            TokenKind::Identifier("c".into()),
+            TokenKind::Keyword(Keyword::Discard),
+            TokenKind::Symbol(Symbol::NotEqual),
+        ];
+        for kind in kinds {
+            assert_eq!(tokenizer.next()?.kind, kind);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_char() -> Result<(), TokenizeError> {
+        let mut tokenizer = Tokenizer::new("x § y".into());
+        tokenizer.next()?;
+
+        let Err(TokenizeError::InvalidChar(slice)) = tokenizer.next() else {
+            panic!("expected an invalid char");
+        };
+        assert_eq!(&*slice.value(), "§");
+
+        Ok(())
+    }
+
+    /// Run with `cargo test --release -- --ignored`, tokenizing twice as much source should take
+    /// about twice as long, where a quadratic tokenizer takes four times as long
+    #[test]
+    #[ignore]
+    fn linear_tokenizing() -> Result<(), TokenizeError> {
+        let line = "func Main(x: i32): f64 => \"héllo\" + 'é' + 0x_FF_u8 * 1.5e3; // wörld\n";
+
+        // The fastest of a few runs, to keep noise out
+        let time = |size: usize| -> Result<Duration, TokenizeError> {
+            let src: Arc<str> = line.repeat(size / line.len()).into();
+            let mut fastest = Duration::MAX;
+
+            for _ in 0..3 {
+                let start = Instant::now();
+                let mut tokenizer = Tokenizer::new(src.clone());
+                while tokenizer.next()?.kind != TokenKind::Eof {}
+
+                fastest = fastest.min(start.elapsed());
+            }
+
+            return Ok(fastest);
+        };
+
+        let mb = 1 << 20;
+        let small = time(mb)?;
+        let large = time(2 * mb)?;
+
+        let ratio = large.as_secs_f64() / small.as_secs_f64();
+        assert!(
+            ratio < 3.0,
+            "doubling the input took {ratio:.2} times as long, {small:?} for 1 MB and {large:?} for 2 MB"
+        );
+
+        Ok(())
+    }
+}
@@ -12,17 +12,33 @@ pub struct Token {
 pub enum TokenKind {
     Identifier(Arc<str>),
     String(Arc<str>),
+    /// `b"..."`
+    ByteString(Arc<[u8]>),
+    /// `c"..."`, including the null terminator
+    CString(Arc<[u8]>),
     Char(char),
     Number(Number),
     Symbol(Symbol),
     Keyword(Keyword),
+    /// `_+_`, `~_`, `_!` and the like, only written after `operator`
+    OperatorTrait(OperatorTrait),
     Eof,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Number {
-    pub whole: u64,
-    pub decimal: f64,
+    /// The literal as written, without its suffix
+    pub text: Arc<str>,
+    pub value: NumberValue,
+    /// One of the numeric primitive keywords, like the `u8` in `255u8`
+    pub suffix: Option<Keyword>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NumberValue {
+    Int(u64),
+    /// Literals with a fraction or an exponent, rounded to the nearest double
+    Float(f64),
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -185,6 +201,108 @@ pub enum Symbol {
     Rest,        // ...
 }
 
+/// The operators that can be overloaded, named by where their operands go
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OperatorTrait {
+    // Binary
+    Add,    // _+_
+    Sub,    // _-_
+    Mul,    // _*_
+    Div,    // _/_
+    Rem,    // _%_
+    BitAnd, // _&_
+    BitOr,  // _|_
+    BitXor, // _^_
+    Shl,    // _<<_
+    Shr,    // _>>_
+
+    // Prefix
+    Neg,     // -_
+    BitNot,  // ~_
+    BoolNot, // !_
+
+    // Postfix
+    Cascade,  // _!
+    Coalesce, // _?
+}
+
+impl OperatorTrait {
+    pub fn binary(symbol: Symbol) -> Option<Self> {
+        return Some(match symbol {
+            Symbol::Add => Self::Add,
+            Symbol::Sub => Self::Sub,
+            Symbol::Mul => Self::Mul,
+            Symbol::Div => Self::Div,
+            Symbol::Rem => Self::Rem,
+            Symbol::BitAnd => Self::BitAnd,
+            Symbol::BitOr => Self::BitOr,
+            Symbol::BitXor => Self::BitXor,
+            Symbol::Shl => Self::Shl,
+            Symbol::Shr => Self::Shr,
+            _ => return None,
+        });
+    }
+
+    pub fn prefix(symbol: Symbol) -> Option<Self> {
+        return Some(match symbol {
+            Symbol::Sub => Self::Neg,
+            Symbol::BitNot => Self::BitNot,
+            Symbol::BoolNot => Self::BoolNot,
+            _ => return None,
+        });
+    }
+
+    pub fn postfix(symbol: Symbol) -> Option<Self> {
+        return Some(match symbol {
+            Symbol::BoolNot => Self::Cascade,
+            Symbol::Optional => Self::Coalesce,
+            _ => return None,
+        });
+    }
+
+    /// How many generic arguments the trait takes, like the type of the right operand in `_+_<T>`
+    /// or `TOk` and `TErr` for `_!`
+    pub fn arity(&self) -> usize {
+        return match self {
+            Self::Add
+            | Self::Sub
+            | Self::Mul
+            | Self::Div
+            | Self::Rem
+            | Self::BitAnd
+            | Self::BitOr
+            | Self::BitXor
+            | Self::Shl
+            | Self::Shr => 1,
+            Self::Neg | Self::BitNot | Self::BoolNot => 0,
+            Self::Cascade => 2,
+            // The type it unwraps to, like the `TOk` of `_!`
+            Self::Coalesce => 1,
+        };
+    }
+
+    /// The name as it's written
+    pub fn as_str(&self) -> &'static str {
+        return match self {
+            Self::Add => "_+_",
+            Self::Sub => "_-_",
+            Self::Mul => "_*_",
+            Self::Div => "_/_",
+            Self::Rem => "_%_",
+            Self::BitAnd => "_&_",
+            Self::BitOr => "_|_",
+            Self::BitXor => "_^_",
+            Self::Shl => "_<<_",
+            Self::Shr => "_>>_",
+            Self::Neg => "-_",
+            Self::BitNot => "~_",
+            Self::BoolNot => "!_",
+            Self::Cascade => "_!",
+            Self::Coalesce => "_?",
+        };
+    }
+}
+
 impl Keyword {
     pub fn from(s: &str) -> Option<Self> {
         return Some(match s {
@@ -251,6 +369,66 @@ impl Keyword {
             _ => return None,
         });
     }
+
+    /// The keyword as it's written
+    pub fn as_str(&self) -> &'static str {
+        return match self {
+            Self::Let => "let",
+            Self::Mut => "mut",
+            Self::Const => "const",
+            Self::Static => "static",
+            Self::Func => "func",
+            Self::Pub => "pub",
+            Self::Using => "using",
+            Self::Ref => "ref",
+            Self::Struct => "struct",
+            Self::Enum => "enum",
+            Self::Trait => "trait",
+            Self::Impl => "impl",
+            Self::Operator => "operator",
+            Self::Where => "where",
+            Self::Namespace => "namespace",
+            Self::Union => "union",
+            Self::Import => "import",
+            Self::Bool => "bool",
+            Self::Char => "char",
+            Self::ThisType => "This",
+            Self::Unit => "unit",
+            Self::Never => "never",
+            Self::Str => "str",
+            Self::I8 => "i8",
+            Self::U8 => "u8",
+            Self::I16 => "i16",
+            Self::U16 => "u16",
+            Self::I32 => "i32",
+            Self::U32 => "u32",
+            Self::I64 => "i64",
+            Self::U64 => "u64",
+            Self::Isize => "isize",
+            Self::Usize => "usize",
+            Self::F32 => "f32",
+            Self::F64 => "f64",
+            Self::ThisValue => "this",
+            Self::Discard => "_",
+            Self::True => "true",
+            Self::False => "false",
+            Self::Default => "default",
+            Self::Sizeof => "sizeof",
+            Self::Nullptr => "nullptr",
+            Self::Invalid => "invalid",
+            Self::For => "for",
+            Self::While => "while",
+            Self::Loop => "loop",
+            Self::If => "if",
+            Self::Else => "else",
+            Self::Match => "match",
+            Self::In => "in",
+            Self::Break => "break",
+            Self::Return => "return",
+            Self::Label => "label",
+            Self::Goto => "goto",
+        };
+    }
 }
 
 impl Symbol {
@@ -273,6 +451,8 @@ impl Symbol {
             "." => Self::Dot,
             "->" => Self::SmallArrow,
             "?" => Self::Optional,
+            // Before `!`, which would otherwise split it in two
+            "!=" => Self::NotEqual,
             "!" => Self::BoolNot,
 
             "=>" => Self::WideArrow,
@@ -288,7 +468,6 @@ impl Symbol {
             ">" => Self::Greater,
             "<=" => Self::LessEqual,
             "<" => Self::Less,
-            "!=" => Self::NotEqual,
             "==" => Self::Equal,
             "=" => Self::Assign,
 
@@ -335,6 +514,71 @@ impl Symbol {
         );
         return None;
     }
+
+    /// The symbol as it's written
+    pub fn as_str(&self) -> &'static str {
+        return match self {
+            Self::Rest => "...",
+            Self::RangeFromTo => "<..=",
+            Self::RangeTo => "..=",
+            Self::RangeFrom => "<..",
+            Self::Range => "..",
+            Self::DoubleColon => "::",
+            Self::Colon => ":",
+            Self::ValueCoalesce => "?.",
+            Self::ValueCascade => "!.",
+            Self::ReferenceCoalesce => "?->",
+            Self::ReferenceCascade => "!->",
+            Self::Dot => ".",
+            Self::SmallArrow => "->",
+            Self::Optional => "?",
+            Self::BoolNot => "!",
+            Self::WideArrow => "=>",
+            Self::Comma => ",",
+            Self::Semicolon => ";",
+            Self::ShlAssign => "<<=",
+            Self::Shl => "<<",
+            Self::ShrAssign => ">>=",
+            Self::Shr => ">>",
+            Self::GreaterEqual => ">=",
+            Self::Greater => ">",
+            Self::LessEqual => "<=",
+            Self::Less => "<",
+            Self::NotEqual => "!=",
+            Self::Equal => "==",
+            Self::Assign => "=",
+            Self::BitOrAssign => "|=",
+            Self::BoolOr => "||",
+            Self::BitOr => "|",
+            Self::BitAndAssign => "&=",
+            Self::BoolAnd => "&&",
+            Self::BitAnd => "&",
+            Self::BitXorAssign => "^=",
+            Self::BoolXor => "^^",
+            Self::BitXor => "^",
+            Self::MulAssign => "*=",
+            Self::Mul => "*",
+            Self::RemAssign => "%=",
+            Self::Rem => "%",
+            Self::DivAssign => "/=",
+            Self::Div => "/",
+            Self::AddAssign => "+=",
+            Self::Increment => "++",
+            Self::Add => "+",
+            Self::SubAssign => "-=",
+            Self::Decrement => "--",
+            Self::Sub => "-",
+            Self::BitNotAssign => "~=",
+            Self::BitNot => "~",
+            Self::BracketOpen => "[",
+            Self::BracketClose => "]",
+            Self::ParenOpen => "(",
+            Self::ParenClose => ")",
+            Self::BraceOpen => "{",
+            Self::BraceClose => "}",
+            Self::Pound => "#",
+        };
+    }
 }
 
 macro symbol_match($parser: expr, $($st: expr => $sy: expr),+ $(,)?) {